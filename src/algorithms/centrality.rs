@@ -0,0 +1,391 @@
+use std::collections::{HashMap, VecDeque};
+
+use rand::seq::index::sample;
+use rand::Rng;
+
+use crate::interfaces::edge::{Hyperedge, Weighted};
+use crate::interfaces::graph::{Adjacency, AdjacencyInv, SingleId};
+use crate::interfaces::hypergraph::Hypergraph;
+
+/// Which edges count at a node: `Out` follows edges away from it, `In` towards it and `Total` ignores direction.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    In,
+    Out,
+    Total,
+}
+
+const TOLERANCE: f64 = 1e-10;
+const MAX_ITERATIONS: usize = 1000;
+
+// Node positions with out- and in-neighbour positions; parallel edges are kept.
+struct Topology<'a, N> {
+    nodes: Vec<&'a N>,
+    out: Vec<Vec<usize>>,
+    inc: Vec<Vec<usize>>,
+}
+
+impl<'a, N> Topology<'a, N> {
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn neighbours(&self, u: usize, direction: Direction) -> impl Iterator<Item = usize> + '_ {
+        let (out, inc): (&[usize], &[usize]) = match direction {
+            Direction::Out => (&self.out[u], &[]),
+            Direction::In => (&[], &self.inc[u]),
+            Direction::Total => (&self.out[u], &self.inc[u]),
+        };
+        out.iter().chain(inc.iter()).copied()
+    }
+
+    // Hop distances from `s`, `None` when unreachable.
+    fn distances(&self, s: usize, direction: Direction) -> Vec<Option<usize>> {
+        let mut dist = vec![None; self.len()];
+        dist[s] = Some(0);
+        let mut queue = VecDeque::from([s]);
+        while let Some(u) = queue.pop_front() {
+            let d = dist[u].unwrap() + 1;
+            for v in self.neighbours(u, direction) {
+                if dist[v].is_none() {
+                    dist[v] = Some(d);
+                    queue.push_back(v);
+                }
+            }
+        }
+        dist
+    }
+
+    // Brandes' dependency accumulation from the single source `s`.
+    fn accumulate(&self, s: usize, betweenness: &mut [f64]) {
+        let n = self.len();
+        let (mut sigma, mut dist, mut delta) = (vec![0.0; n], vec![usize::MAX; n], vec![0.0; n]);
+        let mut preds = vec![Vec::new(); n];
+        let mut stack = Vec::with_capacity(n);
+        sigma[s] = 1.0;
+        dist[s] = 0;
+        let mut queue = VecDeque::from([s]);
+        while let Some(u) = queue.pop_front() {
+            stack.push(u);
+            for &v in self.out[u].iter() {
+                if dist[v] == usize::MAX {
+                    dist[v] = dist[u] + 1;
+                    queue.push_back(v);
+                }
+                if dist[v] == dist[u] + 1 {
+                    sigma[v] += sigma[u];
+                    preds[v].push(u);
+                }
+            }
+        }
+        while let Some(v) = stack.pop() {
+            for &u in preds[v].iter() {
+                delta[u] += sigma[u] / sigma[v] * (1.0 + delta[v]);
+            }
+            if v != s {
+                betweenness[v] += delta[v];
+            }
+        }
+    }
+
+    fn keyed<T>(&self, values: Vec<T>) -> HashMap<&'a N, T>
+    where N: Eq + std::hash::Hash {
+        self.nodes.iter().copied().zip(values).collect()
+    }
+}
+
+fn topology<'a, G: Adjacency<'a> + AdjacencyInv<'a>>(graph: &'a G) -> Topology<'a, G::Node> {
+    let nodes = graph.nodes().collect::<Vec<_>>();
+    let index = nodes.iter().enumerate().map(|(i, &node)| (node, i)).collect::<HashMap<_, _>>();
+    let (adj, adj_inv) = (graph.get_adj(), graph.get_adj_inv());
+    let out = nodes.iter().map(|node| graph.get_post(&adj, node).map(|v| index[v]).collect()).collect();
+    let inc = nodes.iter().map(|node| graph.get_pre(&adj_inv, node).map(|v| index[v]).collect()).collect();
+    Topology { nodes, out, inc }
+}
+
+// The teleport distribution: `personalization` keyed by node id and normalised, uniform when absent or all zero.
+fn teleport(ids: impl Iterator<Item = usize>, personalization: Option<&HashMap<usize, f64>>) -> Vec<f64> {
+    let mut p = ids.map(|id| personalization.map_or(1.0, |p| p.get(&id).copied().unwrap_or(0.0))).collect::<Vec<f64>>();
+    let total = p.iter().sum::<f64>();
+    if total > 0.0 {
+        p.iter_mut().for_each(|x| *x /= total);
+    } else {
+        let n = p.len() as f64;
+        p.iter_mut().for_each(|x| *x = 1.0 / n);
+    }
+    p
+}
+
+// Iterate `step` from the uniform vector until successive vectors differ by at most the tolerance (L1, scaled by `n`).
+// An empty vector is settled at once.
+fn power_iteration(n: usize, mut step: impl FnMut(&[f64]) -> Vec<f64>) -> Option<Vec<f64>> {
+    let mut x = vec![1.0 / n as f64; n];
+    for _ in 0..MAX_ITERATIONS {
+        let next = step(&x);
+        let change = next.iter().zip(x.iter()).map(|(a, b)| (a - b).abs()).sum::<f64>();
+        x = next;
+        if change <= n as f64 * TOLERANCE {
+            return Some(x);
+        }
+    }
+    None
+}
+
+fn normalize_l2(x: &mut [f64]) {
+    let norm = x.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        x.iter_mut().for_each(|v| *v /= norm);
+    }
+}
+
+/// Node centralities of a directed graph, read through `get_post` and `get_pre`. Parallel edges count once each.
+pub trait Centrality<'a>: Adjacency<'a> + AdjacencyInv<'a> {
+    fn degree_centrality(&'a self, direction: Direction) -> HashMap<&'a Self::Node, usize> {
+        let topology = topology(self);
+        let degrees = (0..topology.len()).map(|u| topology.neighbours(u, direction).count()).collect();
+        topology.keyed(degrees)
+    }
+
+    /// `(r - 1) / sum of distances`, scaled by `(r - 1) / (n - 1)` where `r` nodes (itself included) are reachable,
+    /// so that nodes in small components do not score high. Distances are taken along `direction` from each node.
+    fn closeness(&'a self, direction: Direction) -> HashMap<&'a Self::Node, f64> {
+        let topology = topology(self);
+        let n = topology.len();
+        let closeness = (0..n).map(|u| {
+            let reached = topology.distances(u, direction).into_iter().flatten().collect::<Vec<_>>();
+            let total = reached.iter().sum::<usize>();
+            if total == 0 || n < 2 {
+                return 0.0;
+            }
+            let r = (reached.len() - 1) as f64;
+            r / total as f64 * r / (n - 1) as f64
+        }).collect();
+        topology.keyed(closeness)
+    }
+
+    /// The sum of reciprocal distances to every other node, with unreachable nodes adding nothing.
+    fn harmonic(&'a self, direction: Direction) -> HashMap<&'a Self::Node, f64> {
+        let topology = topology(self);
+        let harmonic = (0..topology.len()).map(|u| {
+            topology.distances(u, direction).into_iter().flatten().filter(|&d| d > 0).map(|d| 1.0 / d as f64).sum()
+        }).collect();
+        topology.keyed(harmonic)
+    }
+
+    /// Brandes' algorithm over shortest directed paths. `normalized` divides by `(n - 1)(n - 2)`.
+    fn betweenness(&'a self, normalized: bool) -> HashMap<&'a Self::Node, f64> {
+        let topology = topology(self);
+        let n = topology.len();
+        let mut betweenness = vec![0.0; n];
+        for s in 0..n {
+            topology.accumulate(s, &mut betweenness);
+        }
+        scale_betweenness(&mut betweenness, n, 1.0, normalized);
+        topology.keyed(betweenness)
+    }
+
+    /// Betweenness estimated from `samples` distinct random sources and scaled up to all `n`.
+    fn betweenness_sampled(&'a self, samples: usize, normalized: bool, rng: &mut impl Rng) -> HashMap<&'a Self::Node, f64> {
+        let topology = topology(self);
+        let n = topology.len();
+        let samples = samples.min(n);
+        let mut betweenness = vec![0.0; n];
+        for s in sample(rng, n, samples) {
+            topology.accumulate(s, &mut betweenness);
+        }
+        let scale = if samples == 0 { 0.0 } else { n as f64 / samples as f64 };
+        scale_betweenness(&mut betweenness, n, scale, normalized);
+        topology.keyed(betweenness)
+    }
+
+    /// PageRank with damping `damping` (usually 0.85). Teleports, and the rank of nodes without out-edges, go to
+    /// `personalization` (keyed by node id) or uniformly when it is `None`. Scores sum to 1.
+    ///
+    /// The iteration always settles for `damping < 1`; `None` if it does not, e.g. for `damping` at 1 on a periodic graph.
+    fn pagerank(&'a self, damping: f64, personalization: Option<&HashMap<usize, f64>>) -> Option<HashMap<&'a Self::Node, f64>> {
+        let topology = topology(self);
+        let n = topology.len();
+        let p = teleport(topology.nodes.iter().map(|node| node.id()), personalization);
+        let ranks = power_iteration(n, |x| {
+            let dangling = (0..n).filter(|&u| topology.out[u].is_empty()).map(|u| x[u]).sum::<f64>();
+            (0..n).map(|v| {
+                let inflow = topology.inc[v].iter().map(|&u| x[u] / topology.out[u].len() as f64).sum::<f64>();
+                (1.0 - damping) * p[v] + damping * (inflow + dangling * p[v])
+            }).collect()
+        })?;
+        Some(topology.keyed(ranks))
+    }
+
+    /// The principal eigenvector of the transposed adjacency matrix, scaled to unit length: a node scores high when
+    /// high-scoring nodes point to it. `None` if the power iteration does not converge.
+    fn eigenvector(&'a self) -> Option<HashMap<&'a Self::Node, f64>> {
+        let topology = topology(self);
+        // Iterating with `A + I` has the same eigenvectors but avoids oscillating on periodic graphs.
+        let scores = power_iteration(topology.len(), |x| {
+            let mut next = (0..x.len()).map(|v| x[v] + topology.inc[v].iter().map(|&u| x[u]).sum::<f64>()).collect::<Vec<_>>();
+            normalize_l2(&mut next);
+            next
+        })?;
+        Some(topology.keyed(scores))
+    }
+
+    /// Katz centrality `x = alpha A^T x + beta`, scaled to unit length. `alpha` must be below the reciprocal of the
+    /// largest eigenvalue for the series to converge; `None` otherwise.
+    fn katz(&'a self, alpha: f64, beta: f64) -> Option<HashMap<&'a Self::Node, f64>> {
+        let topology = topology(self);
+        let mut scores = power_iteration(topology.len(), |x| {
+            (0..x.len()).map(|v| alpha * topology.inc[v].iter().map(|&u| x[u]).sum::<f64>() + beta).collect()
+        })?;
+        normalize_l2(&mut scores);
+        Some(topology.keyed(scores))
+    }
+}
+
+impl<'a, G: Adjacency<'a> + AdjacencyInv<'a>> Centrality<'a> for G {}
+
+fn scale_betweenness(betweenness: &mut [f64], n: usize, scale: f64, normalized: bool) {
+    let scale = if normalized && n > 2 { scale / ((n - 1) * (n - 2)) as f64 } else { scale };
+    betweenness.iter_mut().for_each(|b| *b *= scale);
+}
+
+/// PageRank for the random walk on the incidence structure: from a node, pick one of its hyperedges with probability
+/// proportional to its weight, then a member of that hyperedge uniformly (repeated members count repeatedly).
+/// `None` if the iteration does not settle, as for `Centrality::pagerank`.
+pub trait HypergraphPageRank<'a>: Hypergraph<'a> + Sized
+where Self::Edge: Weighted {
+    fn pagerank(&'a self, damping: f64, personalization: Option<&HashMap<usize, f64>>) -> Option<HashMap<&'a Self::Node, f64>> {
+        let nodes = self.nodes().collect::<Vec<_>>();
+        let n = nodes.len();
+        let index = nodes.iter().enumerate().map(|(i, node)| (node.id(), i)).collect::<HashMap<_, _>>();
+        let edges = self.hyperedges().map(|edge| {
+            let members = edge.sorted_members().iter().filter_map(|id| index.get(id).copied()).collect::<Vec<_>>();
+            (members, edge.weight())
+        }).filter(|(members, _)| !members.is_empty()).collect::<Vec<_>>();
+        let mut strength = vec![0.0; n];
+        for (members, weight) in edges.iter() {
+            for &v in members.iter() {
+                strength[v] += weight;
+            }
+        }
+        let p = teleport(nodes.iter().map(|node| node.id()), personalization);
+
+        let ranks = power_iteration(n, |x| {
+            let dangling = (0..n).filter(|&u| strength[u] <= 0.0).map(|u| x[u]).sum::<f64>();
+            let mut next = p.iter().map(|&pv| (1.0 - damping) * pv + damping * dangling * pv).collect::<Vec<_>>();
+            for (members, weight) in edges.iter() {
+                let mass = members.iter().filter(|&&u| strength[u] > 0.0).map(|&u| x[u] * weight / strength[u]).sum::<f64>();
+                let share = damping * mass / members.len() as f64;
+                for &v in members.iter() {
+                    next[v] += share;
+                }
+            }
+            next
+        })?;
+        Some(nodes.into_iter().zip(ranks).collect())
+    }
+}
+
+impl<'a, H: Hypergraph<'a>> HypergraphPageRank<'a> for H
+where H::Edge: Weighted {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::hypergraph::{HyperedgeImpl, HypergraphImpl, Node, NodeType, Desc};
+    use crate::impls::standard::StandardLabeledGraph;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn by_id<N: SingleId>(scores: HashMap<&N, f64>) -> HashMap<usize, f64> {
+        scores.into_iter().map(|(node, score)| (node.id(), score)).collect()
+    }
+
+    #[test]
+    fn star_and_path() {
+        // 0 -> 1 -> 2 -> 3, and 4, 5 -> 2.
+        let mut graph = StandardLabeledGraph::new();
+        for id in 0..6 {
+            graph.add_node(id, String::new());
+        }
+        for (src, dst) in [(0, 1), (1, 2), (2, 3), (4, 2), (5, 2)] {
+            graph.add_edge(src, dst);
+        }
+
+        let degree = graph.degree_centrality(Direction::In).into_iter().map(|(node, d)| (node.id(), d)).collect::<HashMap<_, _>>();
+        assert_eq!(degree[&2], 3);
+        // Paths through 2: from 0, 1, 4 and 5 to 3. Paths through 1: from 0 to 2 and 3.
+        let betweenness = by_id(graph.betweenness(false));
+        assert_eq!((betweenness[&2], betweenness[&1], betweenness[&0]), (4.0, 2.0, 0.0));
+        assert_eq!(by_id(graph.harmonic(Direction::Out))[&0], 1.0 + 0.5 + 1.0 / 3.0);
+
+        let ranks = by_id(graph.pagerank(0.85, None).unwrap());
+        assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(ranks[&3] > ranks[&2] && ranks[&2] > ranks[&1]);
+        let katz = by_id(graph.katz(0.1, 1.0).unwrap());
+        assert!(katz[&2] > katz[&1] && katz[&1] > katz[&0]);
+
+        // From 0 the out-distances to 1, 2 and 3 are 1, 2 and 3, and 4 of the 6 nodes are reachable.
+        let closeness = by_id(graph.closeness(Direction::Out));
+        assert!((closeness[&0] - 3.0 / 6.0 * 3.0 / 5.0).abs() < 1e-12);
+        assert!((closeness[&2] - 1.0 / 5.0).abs() < 1e-12);
+        assert_eq!(closeness[&3], 0.0);
+
+        // Sampling every node as a source gives the exact betweenness; sampling none gives zeros.
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(by_id(graph.betweenness_sampled(6, false, &mut rng)), by_id(graph.betweenness(false)));
+        assert!(graph.betweenness_sampled(0, true, &mut rng).values().all(|&b| b == 0.0));
+
+        let personal = HashMap::from([(4, 1.0)]);
+        let ranks = by_id(graph.pagerank(0.85, Some(&personal)).unwrap());
+        assert!(ranks[&4] > ranks[&5]);
+    }
+
+    #[test]
+    fn hypergraph_pagerank_is_uniform_on_regular_hypergraphs() {
+        let mut hypergraph = HypergraphImpl::new();
+        for id in 0..4 {
+            hypergraph.add_node(Node::new(id, NodeType::new(0), Desc::default()));
+        }
+        for pins in [vec![0, 1, 2], vec![1, 2, 3], vec![2, 3, 0], vec![3, 0, 1]] {
+            hypergraph.add_hyperedge(HyperedgeImpl::new(pins, 1.0, String::new(), 0));
+        }
+        let ranks = by_id(HypergraphPageRank::pagerank(&hypergraph, 0.85, None).unwrap());
+        assert!(ranks.values().all(|r| (r - 0.25).abs() < 1e-9));
+    }
+
+    #[test]
+    fn eigenvector_of_a_cycle_with_a_tail() {
+        // 0 -> 1 -> 2 -> 0, and 3 -> 0: nothing points to 3.
+        let mut graph = StandardLabeledGraph::new();
+        for id in 0..4 {
+            graph.add_node(id, String::new());
+        }
+        for (src, dst) in [(0, 1), (1, 2), (2, 0), (3, 0)] {
+            graph.add_edge(src, dst);
+        }
+        let scores = by_id(graph.eigenvector().unwrap());
+        assert!((0..3).all(|id| (scores[&id] - 1.0 / 3f64.sqrt()).abs() < 1e-6));
+        assert!(scores[&3] < 1e-6);
+    }
+
+    #[test]
+    fn empty_and_periodic_inputs() {
+        let graph = StandardLabeledGraph::new();
+        assert!(graph.pagerank(0.85, None).unwrap().is_empty());
+        assert!(graph.eigenvector().unwrap().is_empty());
+        assert!(graph.katz(0.1, 1.0).unwrap().is_empty());
+        assert!(graph.closeness(Direction::Out).is_empty());
+        assert!(HypergraphPageRank::pagerank(&HypergraphImpl::new(), 0.85, None).unwrap().is_empty());
+
+        // Without teleporting, the walk on 0 <-> 1 <-> 2 alternates between the middle node and the ends.
+        let mut graph = StandardLabeledGraph::new();
+        for id in 0..3 {
+            graph.add_node(id, String::new());
+        }
+        for (src, dst) in [(0, 1), (1, 0), (1, 2), (2, 1)] {
+            graph.add_edge(src, dst);
+        }
+        assert!(graph.pagerank(1.0, None).is_none());
+        assert!(graph.pagerank(0.85, None).is_some());
+    }
+}
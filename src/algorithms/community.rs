@@ -0,0 +1,546 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::interfaces::edge::{Hyperedge, Weighted};
+use crate::interfaces::graph::{Graph, IdPair, SingleId};
+use crate::interfaces::hypergraph::Hypergraph;
+use crate::interfaces::typed::Type;
+
+/// A community id per node id, with the modularity of that division.
+#[derive(Clone, Debug)]
+pub struct Communities {
+    /// Communities are numbered from 0 in the order their first node appears.
+    pub membership: HashMap<usize, usize>,
+    pub modularity: f64,
+}
+
+impl Communities {
+    pub fn count(&self) -> usize {
+        self.membership.values().max().map_or(0, |&c| c + 1)
+    }
+}
+
+// An undirected weighted graph over `0..adj.len()`. `adj` holds merged neighbour weights; a self-loop entry `(u, w)`
+// means `A_uu = w`, so an undirected loop of weight `w` is stored as `2w`.
+#[derive(Clone)]
+struct Net {
+    adj: Vec<Vec<(usize, f64)>>,
+    degree: Vec<f64>,
+    total: f64,
+}
+
+impl Net {
+    fn from_pairs(n: usize, pairs: impl Iterator<Item = (usize, usize, f64)>) -> Self {
+        let mut maps = vec![HashMap::new(); n];
+        for (u, v, w) in pairs {
+            if u == v {
+                *maps[u].entry(u).or_insert(0.0) += 2.0 * w;
+            } else {
+                *maps[u].entry(v).or_insert(0.0) += w;
+                *maps[v].entry(u).or_insert(0.0) += w;
+            }
+        }
+        Self::from_maps(maps)
+    }
+
+    fn from_maps(maps: Vec<HashMap<usize, f64>>) -> Self {
+        let adj = maps.into_iter().map(|m| {
+            let mut row = m.into_iter().collect::<Vec<_>>();
+            row.sort_unstable_by_key(|&(v, _)| v);
+            row
+        }).collect::<Vec<Vec<_>>>();
+        let degree = adj.iter().map(|row| row.iter().map(|&(_, w)| w).sum()).collect::<Vec<f64>>();
+        let total = degree.iter().sum();
+        Net { adj, degree, total }
+    }
+
+    fn len(&self) -> usize {
+        self.adj.len()
+    }
+
+    // Collapse every community of `part` (numbered `0..count`) into one node.
+    fn aggregate(&self, part: &[usize], count: usize) -> Self {
+        let mut maps = vec![HashMap::new(); count];
+        for (u, row) in self.adj.iter().enumerate() {
+            for &(v, w) in row {
+                *maps[part[u]].entry(part[v]).or_insert(0.0) += w;
+            }
+        }
+        Self::from_maps(maps)
+    }
+
+    fn modularity(&self, part: &[usize], resolution: f64) -> f64 {
+        if self.total == 0.0 {
+            return 0.0;
+        }
+        let count = part.iter().max().map_or(0, |&c| c + 1);
+        let (mut inside, mut tot) = (vec![0.0; count], vec![0.0; count]);
+        for (u, row) in self.adj.iter().enumerate() {
+            tot[part[u]] += self.degree[u];
+            inside[part[u]] += row.iter().filter(|&&(v, _)| part[v] == part[u]).map(|&(_, w)| w).sum::<f64>();
+        }
+        inside.iter().zip(tot.iter()).map(|(i, t)| i / self.total - resolution * (t / self.total).powi(2)).sum()
+    }
+
+    // Weight from `u` to each community among its neighbours, `u` itself excluded.
+    fn neighbour_weights(&self, u: usize, part: &[usize], weights: &mut HashMap<usize, f64>) {
+        weights.clear();
+        for &(v, w) in self.adj[u].iter() {
+            if v != u {
+                *weights.entry(part[v]).or_insert(0.0) += w;
+            }
+        }
+    }
+}
+
+// Renumber communities from 0 in order of first appearance; returns the count.
+fn renumber(part: &mut [usize]) -> usize {
+    let mut ids = HashMap::new();
+    for c in part.iter_mut() {
+        let next = ids.len();
+        *c = *ids.entry(*c).or_insert(next);
+    }
+    ids.len()
+}
+
+// Move single nodes to the neighbouring community with the best modularity gain until nothing moves.
+// With `queue`, only neighbours of moved nodes are revisited, as in Leiden's fast local moving.
+fn local_moving(net: &Net, part: &mut [usize], resolution: f64, queue: bool, rng: &mut StdRng) -> bool {
+    let n = net.len();
+    let mut tot = vec![0.0; n];
+    for u in 0..n {
+        tot[part[u]] += net.degree[u];
+    }
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.shuffle(rng);
+    let mut pending = order.iter().copied().collect::<std::collections::VecDeque<_>>();
+    let mut queued = vec![true; n];
+    let mut weights = HashMap::new();
+    let mut moved_any = false;
+    loop {
+        let mut moved = false;
+        let batch = if queue { pending.drain(..).collect() } else { order.clone() };
+        for u in batch {
+            queued[u] = false;
+            let (current, k) = (part[u], net.degree[u]);
+            net.neighbour_weights(u, part, &mut weights);
+            tot[current] -= k;
+            let gain = |c: usize, w: f64| w - resolution * k * tot[c] / net.total;
+            let mut best = (current, gain(current, *weights.get(&current).unwrap_or(&0.0)));
+            let mut candidates = weights.iter().map(|(&c, &w)| (c, w)).collect::<Vec<_>>();
+            candidates.sort_unstable_by_key(|&(c, _)| c);
+            for (c, w) in candidates {
+                let g = gain(c, w);
+                if g > best.1 + 1e-12 {
+                    best = (c, g);
+                }
+            }
+            tot[best.0] += k;
+            if best.0 != current {
+                part[u] = best.0;
+                moved = true;
+                moved_any = true;
+                if queue {
+                    for &(v, _) in net.adj[u].iter() {
+                        if !queued[v] && part[v] != best.0 {
+                            queued[v] = true;
+                            pending.push_back(v);
+                        }
+                    }
+                }
+            }
+        }
+        if queue && pending.is_empty() || !queue && !moved {
+            return moved_any;
+        }
+    }
+}
+
+// Leiden's refinement: inside each community, merge well-connected singletons into well-connected sub-communities,
+// so that every community of the next level is internally connected.
+fn refine(net: &Net, part: &[usize], resolution: f64, rng: &mut StdRng) -> Vec<usize> {
+    let n = net.len();
+    let count = part.iter().max().map_or(0, |&c| c + 1);
+    let mut community_tot = vec![0.0; count];
+    for u in 0..n {
+        community_tot[part[u]] += net.degree[u];
+    }
+    // Weight from each node (or sub-community) to the rest of its community.
+    let mut refined = (0..n).collect::<Vec<_>>();
+    let mut sub_tot = net.degree.clone();
+    let mut sub_out = (0..n).map(|u| net.adj[u].iter().filter(|&&(v, _)| v != u && part[v] == part[u]).map(|&(_, w)| w).sum()).collect::<Vec<f64>>();
+    let mut singleton = vec![true; n];
+    let well_connected = |out: f64, tot: f64, c: usize| out >= resolution * tot * (community_tot[c] - tot) / net.total;
+
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.shuffle(rng);
+    let mut weights = HashMap::new();
+    for u in order {
+        let c = part[u];
+        if !singleton[u] || !well_connected(sub_out[u], net.degree[u], c) {
+            continue;
+        }
+        weights.clear();
+        for &(v, w) in net.adj[u].iter() {
+            if v != u && part[v] == c {
+                *weights.entry(refined[v]).or_insert(0.0) += w;
+            }
+        }
+        let k = net.degree[u];
+        let mut best: Option<(usize, f64)> = None;
+        let mut candidates = weights.iter().map(|(&s, &w)| (s, w)).collect::<Vec<_>>();
+        candidates.sort_unstable_by_key(|&(s, _)| s);
+        for (s, w) in candidates {
+            if s == refined[u] || !well_connected(sub_out[s], sub_tot[s], c) {
+                continue;
+            }
+            let gain = w - resolution * k * sub_tot[s] / net.total;
+            if gain >= 0.0 && best.is_none_or(|(_, g)| gain > g) {
+                best = Some((s, gain));
+            }
+        }
+        if let Some((s, w_us)) = best.map(|(s, _)| (s, weights[&s])) {
+            let own = refined[u];
+            refined[u] = s;
+            sub_tot[s] += k;
+            sub_tot[own] -= k;
+            // Edges between `u` and `s` become internal to the merged sub-community.
+            sub_out[s] += sub_out[u] - 2.0 * w_us;
+            singleton[u] = false;
+            singleton[s] = false;
+        }
+    }
+    refined
+}
+
+fn louvain(base: &Net, resolution: f64, seed: u64) -> Vec<usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut net = base.clone();
+    let mut membership = (0..base.len()).collect::<Vec<_>>();
+    loop {
+        let mut part = (0..net.len()).collect::<Vec<_>>();
+        let moved = local_moving(&net, &mut part, resolution, false, &mut rng);
+        let count = renumber(&mut part);
+        for m in membership.iter_mut() {
+            *m = part[*m];
+        }
+        if !moved || count == net.len() {
+            return membership;
+        }
+        net = net.aggregate(&part, count);
+    }
+}
+
+fn leiden(base: &Net, resolution: f64, seed: u64) -> Vec<usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut net = base.clone();
+    let mut membership = (0..base.len()).collect::<Vec<_>>();
+    let mut part = (0..net.len()).collect::<Vec<_>>();
+    loop {
+        local_moving(&net, &mut part, resolution, true, &mut rng);
+        let count = renumber(&mut part);
+        if count == net.len() {
+            return membership.iter().map(|&m| part[m]).collect();
+        }
+        let mut refined = refine(&net, &part, resolution, &mut rng);
+        let mut refined_count = renumber(&mut refined);
+        // Refinement that merges nothing would aggregate to the same network, so fall back to the communities.
+        if refined_count == net.len() {
+            refined = part.clone();
+            refined_count = count;
+        }
+        let mut next = vec![0; refined_count];
+        for u in 0..net.len() {
+            next[refined[u]] = part[u];
+        }
+        for m in membership.iter_mut() {
+            *m = refined[*m];
+        }
+        net = net.aggregate(&refined, refined_count);
+        part = next;
+    }
+}
+
+fn label_propagation(net: &Net, seed: u64) -> Vec<usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let n = net.len();
+    let mut label = (0..n).collect::<Vec<_>>();
+    let mut order = (0..n).collect::<Vec<_>>();
+    let mut weights = HashMap::new();
+    for _ in 0..100 {
+        order.shuffle(&mut rng);
+        let mut changed = false;
+        for &u in order.iter() {
+            net.neighbour_weights(u, &label, &mut weights);
+            let Some(max) = weights.values().copied().reduce(f64::max) else {
+                continue;
+            };
+            let mut best = weights.iter().filter(|&(_, &w)| w >= max - 1e-12).map(|(&l, _)| l).collect::<Vec<_>>();
+            if best.contains(&label[u]) {
+                continue;
+            }
+            best.sort_unstable();
+            label[u] = best[rng.random_range(0..best.len())];
+            changed = true;
+        }
+        if !changed {
+            break;
+        }
+    }
+    renumber(&mut label);
+    label
+}
+
+fn communities(ids: &[usize], net: &Net, mut part: Vec<usize>) -> Communities {
+    renumber(&mut part);
+    let modularity = net.modularity(&part, 1.0);
+    Communities { membership: ids.iter().copied().zip(part).collect(), modularity }
+}
+
+// Edges with an endpoint that is not a node are skipped.
+fn graph_net<'a, G: Graph<'a>>(graph: &'a G) -> (Vec<usize>, Net) {
+    let ids = graph.nodes().map(|node| node.id()).collect::<Vec<_>>();
+    let index = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect::<HashMap<_, _>>();
+    let pairs = graph.edges().filter_map(|edge| {
+        let (src, dst) = edge.pair();
+        Some((*index.get(&src)?, *index.get(&dst)?, 1.0))
+    });
+    let net = Net::from_pairs(ids.len(), pairs);
+    (ids, net)
+}
+
+// Clique expansion: a hyperedge of weight `w` with `s` members links every pair of them with weight `w / (s - 1)`.
+// Hyperedges with a member that is not a node are skipped.
+fn hypergraph_net<'a, H: Hypergraph<'a>>(hypergraph: &'a H) -> (Vec<usize>, Net)
+where H::Edge: Weighted {
+    let ids = hypergraph.nodes().map(|node| node.id()).collect::<Vec<_>>();
+    let index = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect::<HashMap<_, _>>();
+    let mut pairs = Vec::new();
+    for edge in hypergraph.hyperedges() {
+        let Some(members) = edge.sorted_ids().iter().map(|id| index.get(id).copied()).collect::<Option<Vec<_>>>() else {
+            continue;
+        };
+        if members.len() < 2 {
+            continue;
+        }
+        let w = edge.weight() / (members.len() - 1) as f64;
+        for (i, &u) in members.iter().enumerate() {
+            for &v in members[i + 1..].iter() {
+                pairs.push((u, v, w));
+            }
+        }
+    }
+    let net = Net::from_pairs(ids.len(), pairs.into_iter());
+    (ids, net)
+}
+
+/// Community detection on a graph, with edges read as undirected and of weight 1. `resolution` above 1 favours
+/// smaller communities; the reported modularity is always the standard one (resolution 1).
+pub trait CommunityDetection<'a>: Graph<'a> + Sized {
+    fn louvain(&'a self, resolution: f64, seed: u64) -> Communities {
+        let (ids, net) = graph_net(self);
+        let part = louvain(&net, resolution, seed);
+        communities(&ids, &net, part)
+    }
+
+    /// Like Louvain, but communities are refined before aggregation so that every community is connected.
+    fn leiden(&'a self, resolution: f64, seed: u64) -> Communities {
+        let (ids, net) = graph_net(self);
+        let part = leiden(&net, resolution, seed);
+        communities(&ids, &net, part)
+    }
+
+    /// Asynchronous label propagation: each node in turn adopts the label most common among its neighbours.
+    fn label_propagation(&'a self, seed: u64) -> Communities {
+        let (ids, net) = graph_net(self);
+        let part = label_propagation(&net, seed);
+        communities(&ids, &net, part)
+    }
+
+    /// The modularity of `membership`, or `None` if it leaves a node without a community.
+    fn modularity(&'a self, membership: &HashMap<usize, usize>) -> Option<f64> {
+        let (ids, net) = graph_net(self);
+        let part = ids.iter().map(|id| membership.get(id).copied()).collect::<Option<Vec<_>>>()?;
+        Some(net.modularity(&part, 1.0))
+    }
+}
+
+impl<'a, G: Graph<'a>> CommunityDetection<'a> for G {}
+
+/// The same algorithms on the weighted clique expansion of a hypergraph.
+pub trait HypergraphCommunityDetection<'a>: Hypergraph<'a> + Sized
+where Self::Edge: Weighted {
+    fn louvain(&'a self, resolution: f64, seed: u64) -> Communities {
+        let (ids, net) = hypergraph_net(self);
+        let part = louvain(&net, resolution, seed);
+        communities(&ids, &net, part)
+    }
+
+    fn leiden(&'a self, resolution: f64, seed: u64) -> Communities {
+        let (ids, net) = hypergraph_net(self);
+        let part = leiden(&net, resolution, seed);
+        communities(&ids, &net, part)
+    }
+
+    fn label_propagation(&'a self, seed: u64) -> Communities {
+        let (ids, net) = hypergraph_net(self);
+        let part = label_propagation(&net, seed);
+        communities(&ids, &net, part)
+    }
+
+    fn modularity(&'a self, membership: &HashMap<usize, usize>) -> Option<f64> {
+        let (ids, net) = hypergraph_net(self);
+        let part = ids.iter().map(|id| membership.get(id).copied()).collect::<Option<Vec<_>>>()?;
+        Some(net.modularity(&part, 1.0))
+    }
+}
+
+impl<'a, H: Hypergraph<'a>> HypergraphCommunityDetection<'a> for H
+where H::Edge: Weighted {}
+
+/// Ground-truth communities from node types, e.g. the planted clusters of generated hypergraphs.
+pub fn type_membership<'n, N: SingleId + Type + 'n>(nodes: impl IntoIterator<Item = &'n N>) -> HashMap<usize, usize> {
+    nodes.into_iter().map(|node| (node.id(), node.type_id())).collect()
+}
+
+type Counts = HashMap<usize, f64>;
+
+// Contingency table of two labelings over the node ids they share, with its marginals and total.
+fn contingency(x: &HashMap<usize, usize>, y: &HashMap<usize, usize>) -> (HashMap<(usize, usize), f64>, Counts, Counts, f64) {
+    let (mut joint, mut a, mut b, mut n) = (HashMap::new(), HashMap::new(), HashMap::new(), 0.0);
+    for (id, &cx) in x.iter() {
+        if let Some(&cy) = y.get(id) {
+            *joint.entry((cx, cy)).or_insert(0.0) += 1.0;
+            *a.entry(cx).or_insert(0.0) += 1.0;
+            *b.entry(cy).or_insert(0.0) += 1.0;
+            n += 1.0;
+        }
+    }
+    (joint, a, b, n)
+}
+
+/// Normalized mutual information, `2 I(X; Y) / (H(X) + H(Y))`. 1 for identical divisions, 0 for independent ones.
+pub fn nmi(x: &HashMap<usize, usize>, y: &HashMap<usize, usize>) -> f64 {
+    let (joint, a, b, n) = contingency(x, y);
+    let entropy = |counts: &Counts| -counts.values().map(|&c| c / n * (c / n).ln()).sum::<f64>();
+    let (hx, hy) = (entropy(&a), entropy(&b));
+    if hx + hy == 0.0 {
+        return 1.0;
+    }
+    let mutual = joint.iter().map(|(&(cx, cy), &c)| c / n * (c * n / (a[&cx] * b[&cy])).ln()).sum::<f64>();
+    2.0 * mutual / (hx + hy)
+}
+
+/// Adjusted Rand index: 1 for identical divisions, about 0 for random ones. With fewer than two shared nodes there is no
+/// pair to disagree on, so it is 1.
+pub fn ari(x: &HashMap<usize, usize>, y: &HashMap<usize, usize>) -> f64 {
+    let (joint, a, b, n) = contingency(x, y);
+    if n < 2.0 {
+        return 1.0;
+    }
+    let pairs = |c: f64| c * (c - 1.0) / 2.0;
+    let index = joint.values().map(|&c| pairs(c)).sum::<f64>();
+    let (sum_a, sum_b) = (a.values().map(|&c| pairs(c)).sum::<f64>(), b.values().map(|&c| pairs(c)).sum::<f64>());
+    let expected = sum_a * sum_b / pairs(n);
+    let max = (sum_a + sum_b) / 2.0;
+    if max == expected {
+        return 1.0;
+    }
+    (index - expected) / (max - expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::hypergraph::{Desc, HyperedgeImpl, HypergraphImpl, Node, NodeType};
+    use crate::impls::standard::{SingleLabel, StandardUnDirectedGraph};
+    use crate::interfaces::edge::NodeSet;
+
+    #[test]
+    fn recovers_planted_communities() {
+        // Four cliques of eight nodes joined in a ring by single edges.
+        let mut graph = StandardUnDirectedGraph::new();
+        for id in 0..32 {
+            graph.add_node(id, format!("{}", id / 8));
+        }
+        for c in 0..4 {
+            for i in 0..8 {
+                for j in i + 1..8 {
+                    graph.add_edge(c * 8 + i, c * 8 + j, SingleLabel::default());
+                }
+            }
+            graph.add_edge(c * 8, ((c + 1) % 4) * 8 + 1, SingleLabel::default());
+        }
+        let truth = (0..32).map(|id| (id as usize, id as usize / 8)).collect::<HashMap<_, _>>();
+
+        for found in [graph.louvain(1.0, 1), graph.leiden(1.0, 1), graph.label_propagation(1)] {
+            assert_eq!(found.count(), 4);
+            assert!((nmi(&found.membership, &truth) - 1.0).abs() < 1e-9);
+            assert!((ari(&found.membership, &truth) - 1.0).abs() < 1e-9);
+            assert!((found.modularity - graph.modularity(&truth).unwrap()).abs() < 1e-9);
+        }
+        let split = (0..32).map(|id| (id as usize, id as usize % 2)).collect::<HashMap<_, _>>();
+        assert!(ari(&split, &truth).abs() < 0.1);
+    }
+
+    #[test]
+    fn recovers_type_planted_clusters() {
+        // Four clusters of ten nodes, planted as node types `id % 4`, with a few light hyperedges across them.
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut hypergraph = HypergraphImpl::new();
+        for id in 0..40 {
+            hypergraph.add_node(Node::new(id, NodeType::new(id % 4), Desc::default()));
+        }
+        for _ in 0..120 {
+            let cluster = rng.random_range(0..4);
+            let pins = (0..3).map(|_| cluster + 4 * rng.random_range(0..10)).collect();
+            hypergraph.add_hyperedge(HyperedgeImpl::from_nodes(pins));
+        }
+        for _ in 0..4 {
+            let mut edge = HyperedgeImpl::from_nodes(vec![rng.random_range(0..40), rng.random_range(0..40)]);
+            edge.set_weight(0.2);
+            hypergraph.add_hyperedge(edge);
+        }
+        let truth = type_membership(hypergraph.nodes());
+
+        for found in [hypergraph.louvain(1.0, 3), hypergraph.leiden(1.0, 3)] {
+            assert_eq!(found.count(), 4);
+            assert!((nmi(&found.membership, &truth) - 1.0).abs() < 1e-9);
+            assert!((ari(&found.membership, &truth) - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn skips_dangling_edges() {
+        let mut graph = StandardUnDirectedGraph::new();
+        let mut hypergraph = HypergraphImpl::new();
+        for id in 0..4 {
+            graph.add_node(id as u64, String::new());
+            hypergraph.add_node(Node::new(id, NodeType::new(0), Desc::default()));
+        }
+        for (u, v) in [(0, 1), (2, 3), (3, 9)] {
+            graph.add_edge(u as u64, v as u64, SingleLabel::default());
+            hypergraph.add_hyperedge(HyperedgeImpl::from_nodes(vec![u, v]));
+        }
+        let truth = HashMap::from([(0, 0), (1, 0), (2, 1), (3, 1)]);
+
+        let found = CommunityDetection::louvain(&graph, 1.0, 1);
+        assert!((ari(&found.membership, &truth) - 1.0).abs() < 1e-9);
+        assert_eq!(CommunityDetection::modularity(&graph, &truth), Some(0.5));
+        let found = HypergraphCommunityDetection::leiden(&hypergraph, 1.0, 1);
+        assert!((ari(&found.membership, &truth) - 1.0).abs() < 1e-9);
+        assert_eq!(HypergraphCommunityDetection::modularity(&hypergraph, &truth), Some(0.5));
+        assert_eq!(CommunityDetection::modularity(&graph, &HashMap::from([(0, 0)])), None);
+    }
+
+    #[test]
+    fn scores_of_tiny_divisions() {
+        let one = HashMap::from([(0, 0)]);
+        assert_eq!(ari(&one, &HashMap::from([(0, 5)])), 1.0);
+        assert_eq!(ari(&HashMap::new(), &HashMap::new()), 1.0);
+        assert_eq!(nmi(&one, &one), 1.0);
+    }
+}
@@ -0,0 +1,424 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+use crate::interfaces::graph::{Directed, Graph, IdPair, SingleId, UnDirected};
+
+const EPSILON: f64 = 1e-12;
+
+/// A maximum `s`-`t` flow. `edge_flows[i]` is the flow on the `i`-th edge of `Graph::edges`; the cut separates the nodes
+/// still reachable from the source in the residual network from the rest, and its capacity equals `value`.
+#[derive(Clone, Debug)]
+pub struct MaxFlow<'a, N> {
+    pub value: f64,
+    pub edge_flows: Vec<f64>,
+    pub source_side: Vec<&'a N>,
+    pub sink_side: Vec<&'a N>,
+}
+
+/// A minimum cut of an undirected graph: the weight of the edges between `side` and `other`.
+#[derive(Clone, Debug)]
+pub struct GlobalCut<'a, N> {
+    pub value: f64,
+    pub side: Vec<&'a N>,
+    pub other: Vec<&'a N>,
+}
+
+// A residual network. Arc `2i` is the `i`-th edge and arc `2i + 1` its reverse, so `a ^ 1` is the partner of `a`.
+struct Residual {
+    head: Vec<usize>,
+    capacity: Vec<f64>,
+    arcs: Vec<Vec<usize>>,
+}
+
+impl Residual {
+    fn len(&self) -> usize {
+        self.arcs.len()
+    }
+
+    fn edge_flows(&self) -> Vec<f64> {
+        (0..self.head.len() / 2).map(|e| self.capacity[2 * e + 1]).collect()
+    }
+
+    fn reachable(&self, s: usize) -> Vec<bool> {
+        let mut seen = vec![false; self.len()];
+        seen[s] = true;
+        let mut queue = VecDeque::from([s]);
+        while let Some(u) = queue.pop_front() {
+            for &a in self.arcs[u].iter() {
+                let v = self.head[a];
+                if self.capacity[a] > EPSILON && !seen[v] {
+                    seen[v] = true;
+                    queue.push_back(v);
+                }
+            }
+        }
+        seen
+    }
+
+    fn dinic(&mut self, s: usize, t: usize) -> f64 {
+        let n = self.len();
+        let mut value = 0.0;
+        loop {
+            let mut level = vec![usize::MAX; n];
+            level[s] = 0;
+            let mut queue = VecDeque::from([s]);
+            while let Some(u) = queue.pop_front() {
+                for &a in self.arcs[u].iter() {
+                    let v = self.head[a];
+                    if self.capacity[a] > EPSILON && level[v] == usize::MAX {
+                        level[v] = level[u] + 1;
+                        queue.push_back(v);
+                    }
+                }
+            }
+            if level[t] == usize::MAX {
+                return value;
+            }
+            // Blocking flow by repeated augmenting paths in the level graph, without recursion.
+            let mut next = vec![0; n];
+            let mut path: Vec<usize> = Vec::new();
+            let mut u = s;
+            loop {
+                if u == t {
+                    let bottleneck = path.iter().map(|&a| self.capacity[a]).fold(f64::INFINITY, f64::min);
+                    for &a in path.iter() {
+                        self.capacity[a] -= bottleneck;
+                        self.capacity[a ^ 1] += bottleneck;
+                    }
+                    value += bottleneck;
+                    path.clear();
+                    u = s;
+                    continue;
+                }
+                while next[u] < self.arcs[u].len() {
+                    let a = self.arcs[u][next[u]];
+                    if self.capacity[a] > EPSILON && level[self.head[a]] == level[u] + 1 {
+                        break;
+                    }
+                    next[u] += 1;
+                }
+                if next[u] < self.arcs[u].len() {
+                    let a = self.arcs[u][next[u]];
+                    path.push(a);
+                    u = self.head[a];
+                } else if let Some(a) = path.pop() {
+                    // `u` is a dead end for this phase.
+                    level[u] = usize::MAX;
+                    u = self.head[a ^ 1];
+                    next[u] += 1;
+                } else {
+                    break;
+                }
+            }
+        }
+    }
+
+    // FIFO push-relabel with the gap heuristic. Heights may exceed `n`, which returns excess that cannot reach the sink
+    // to the source, so the result is a flow and not only a preflow.
+    fn push_relabel(&mut self, s: usize, t: usize) -> f64 {
+        let n = self.len();
+        let mut height = vec![0; n];
+        let mut count = vec![0; 2 * n + 1];
+        let mut excess = vec![0.0; n];
+        let mut active = vec![false; n];
+        let mut queue = VecDeque::new();
+        height[s] = n;
+        count[0] = n - 1;
+        count[n] = 1;
+        for i in 0..self.arcs[s].len() {
+            let a = self.arcs[s][i];
+            let (v, c) = (self.head[a], self.capacity[a]);
+            if c > EPSILON {
+                self.capacity[a] = 0.0;
+                self.capacity[a ^ 1] += c;
+                excess[v] += c;
+                excess[s] -= c;
+                if v != t && v != s && !active[v] {
+                    active[v] = true;
+                    queue.push_back(v);
+                }
+            }
+        }
+        let mut next = vec![0; n];
+        while let Some(u) = queue.pop_front() {
+            active[u] = false;
+            while excess[u] > EPSILON {
+                if next[u] == self.arcs[u].len() {
+                    // Relabel to one above the lowest residual neighbour.
+                    let old = height[u];
+                    let lowest = self.arcs[u].iter().filter(|&&a| self.capacity[a] > EPSILON).map(|&a| height[self.head[a]]).min();
+                    let Some(lowest) = lowest else { break };
+                    height[u] = (lowest + 1).min(2 * n);
+                    count[old] -= 1;
+                    count[height[u]] += 1;
+                    next[u] = 0;
+                    if count[old] == 0 && old < n {
+                        // Nothing between `old` and `n` can reach the sink any more.
+                        for v in 0..n {
+                            if v != s && height[v] > old && height[v] < n {
+                                count[height[v]] -= 1;
+                                height[v] = n + 1;
+                                count[n + 1] += 1;
+                                next[v] = 0;
+                            }
+                        }
+                    }
+                    continue;
+                }
+                let a = self.arcs[u][next[u]];
+                let v = self.head[a];
+                if self.capacity[a] > EPSILON && height[u] == height[v] + 1 {
+                    let pushed = excess[u].min(self.capacity[a]);
+                    self.capacity[a] -= pushed;
+                    self.capacity[a ^ 1] += pushed;
+                    excess[u] -= pushed;
+                    excess[v] += pushed;
+                    if v != s && v != t && !active[v] {
+                        active[v] = true;
+                        queue.push_back(v);
+                    }
+                } else {
+                    next[u] += 1;
+                }
+            }
+        }
+        excess[t]
+    }
+}
+
+type Prepared<'a, N> = (Vec<&'a N>, Residual, usize, usize);
+
+fn residual<'a, G: Graph<'a>>(graph: &'a G, source: &G::Node, sink: &G::Node, capacity: impl Fn(usize, &G::Edge) -> f64) -> Option<Prepared<'a, G::Node>> {
+    let nodes = graph.nodes().collect::<Vec<_>>();
+    let index = nodes.iter().enumerate().map(|(i, node)| (node.id(), i)).collect::<HashMap<_, _>>();
+    let (s, t) = (*index.get(&source.id())?, *index.get(&sink.id())?);
+    let mut network = Residual { head: Vec::new(), capacity: Vec::new(), arcs: vec![Vec::new(); nodes.len()] };
+    for (i, edge) in graph.edges().enumerate() {
+        let (u, v) = edge.pair();
+        let arc = network.head.len();
+        network.capacity.extend([capacity(i, edge).max(0.0), 0.0]);
+        // Self-loops and edges with an endpoint that is not a node stay in the arc arrays, so edge positions line up,
+        // but never carry flow.
+        match (index.get(&u), index.get(&v)) {
+            (Some(&u), Some(&v)) if u != v => {
+                network.head.extend([v, u]);
+                network.arcs[u].push(arc);
+                network.arcs[v].push(arc + 1);
+            }
+            _ => network.head.extend([s, s]),
+        }
+    }
+    Some((nodes, network, s, t))
+}
+
+fn max_flow<'a, N>(nodes: Vec<&'a N>, network: Residual, s: usize, value: f64) -> MaxFlow<'a, N> {
+    let reachable = network.reachable(s);
+    let (source_side, sink_side) = nodes.into_iter().zip(reachable).partition::<Vec<_>, _>(|&(_, r)| r);
+    MaxFlow {
+        value,
+        edge_flows: network.edge_flows(),
+        source_side: source_side.into_iter().map(|(node, _)| node).collect(),
+        sink_side: sink_side.into_iter().map(|(node, _)| node).collect(),
+    }
+}
+
+/// Maximum flows in a directed graph. `capacity` gives each edge's capacity from its position in `Graph::edges` and
+/// the edge itself, e.g. a `PropertyGraph` edge property; negative capacities count as 0. Both algorithms find the
+/// same value and a minimum cut, though the per-edge flows may differ.
+///
+/// Both return `None` if the source or the sink is not in the graph. A flow from a node to itself has value 0, and
+/// edges with an endpoint that is not a node carry no flow.
+pub trait Flow<'a>: Graph<'a> + Directed + Sized {
+    fn dinic(&'a self, source: &Self::Node, sink: &Self::Node, capacity: impl Fn(usize, &Self::Edge) -> f64) -> Option<MaxFlow<'a, Self::Node>> {
+        let (nodes, mut network, s, t) = residual(self, source, sink, capacity)?;
+        let value = if s == t { 0.0 } else { network.dinic(s, t) };
+        Some(max_flow(nodes, network, s, value))
+    }
+
+    fn push_relabel(&'a self, source: &Self::Node, sink: &Self::Node, capacity: impl Fn(usize, &Self::Edge) -> f64) -> Option<MaxFlow<'a, Self::Node>> {
+        let (nodes, mut network, s, t) = residual(self, source, sink, capacity)?;
+        let value = if s == t { 0.0 } else { network.push_relabel(s, t) };
+        Some(max_flow(nodes, network, s, value))
+    }
+}
+
+impl<'a, G: Graph<'a> + Directed> Flow<'a> for G {}
+
+#[derive(PartialEq)]
+struct Key(f64, usize);
+
+impl Eq for Key {}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(other.1.cmp(&self.1))
+    }
+}
+
+/// Global minimum cuts of an undirected graph with non-negative edge weights.
+pub trait MinCut<'a>: Graph<'a> + UnDirected + Sized {
+    /// Stoer-Wagner. `weight` is read like the capacities of `Flow`; parallel edges add up, and self-loops and edges
+    /// with an endpoint that is not a node are ignored.
+    /// `None` for graphs with fewer than two nodes.
+    fn stoer_wagner(&'a self, weight: impl Fn(usize, &Self::Edge) -> f64) -> Option<GlobalCut<'a, Self::Node>> {
+        let nodes = self.nodes().collect::<Vec<_>>();
+        let n = nodes.len();
+        if n < 2 {
+            return None;
+        }
+        let index = nodes.iter().enumerate().map(|(i, node)| (node.id(), i)).collect::<HashMap<_, _>>();
+        let mut adj = vec![HashMap::new(); n];
+        for (i, edge) in self.edges().enumerate() {
+            let (u, v) = edge.pair();
+            let (Some(&u), Some(&v)) = (index.get(&u), index.get(&v)) else {
+                continue;
+            };
+            if u != v {
+                let w = weight(i, edge).max(0.0);
+                *adj[u].entry(v).or_insert(0.0) += w;
+                *adj[v].entry(u).or_insert(0.0) += w;
+            }
+        }
+        let mut members = (0..n).map(|u| vec![u]).collect::<Vec<_>>();
+        let mut alive = vec![true; n];
+        let mut best: Option<(f64, Vec<usize>)> = None;
+
+        for phase in 0..n - 1 {
+            // Maximum adjacency order; the last two vertices added are merged.
+            // Every live vertex starts in the heap, so disconnected parts are reached too.
+            let mut attached = vec![0.0; n];
+            let mut added = vec![false; n];
+            let mut heap = (0..n).filter(|&u| alive[u]).map(|u| Key(0.0, u)).collect::<BinaryHeap<_>>();
+            let (mut previous, mut last) = (usize::MAX, usize::MAX);
+            let mut last_weight = 0.0;
+            for _ in 0..n - phase {
+                let u = loop {
+                    let Key(w, u) = heap.pop().unwrap();
+                    if !added[u] && w == attached[u] {
+                        last_weight = w;
+                        break u;
+                    }
+                };
+                added[u] = true;
+                previous = last;
+                last = u;
+                for (&v, &w) in adj[u].iter() {
+                    if !added[v] {
+                        attached[v] += w;
+                        heap.push(Key(attached[v], v));
+                    }
+                }
+            }
+            if best.as_ref().is_none_or(|(value, _)| last_weight < *value) {
+                best = Some((last_weight, members[last].clone()));
+            }
+            // Merge `last` into `previous`.
+            let edges = std::mem::take(&mut adj[last]);
+            for (v, w) in edges {
+                adj[v].remove(&last);
+                if v != previous {
+                    *adj[previous].entry(v).or_insert(0.0) += w;
+                    *adj[v].entry(previous).or_insert(0.0) += w;
+                }
+            }
+            let moved = std::mem::take(&mut members[last]);
+            members[previous].extend(moved);
+            alive[last] = false;
+        }
+
+        let (value, side) = best.unwrap();
+        let mut in_side = vec![false; n];
+        side.into_iter().for_each(|u| in_side[u] = true);
+        let (side, other) = nodes.into_iter().zip(in_side).partition::<Vec<_>, _>(|&(_, s)| s);
+        Some(GlobalCut {
+            value,
+            side: side.into_iter().map(|(node, _)| node).collect(),
+            other: other.into_iter().map(|(node, _)| node).collect(),
+        })
+    }
+}
+
+impl<'a, G: Graph<'a> + UnDirected> MinCut<'a> for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::property::{Properties, PropertyGraph};
+    use crate::impls::standard::{SingleLabel, StandardLabeledGraph, StandardUnDirectedGraph};
+
+    #[test]
+    fn flows_match_and_cut_is_tight() {
+        // The classic CLRS network, capacities stored as edge properties.
+        let mut graph = PropertyGraph::<String, String>::new();
+        for id in 0..6 {
+            graph.add_node(id, String::new(), Properties::new());
+        }
+        for (src, dst, capacity) in [(0, 1, 16), (0, 2, 13), (2, 1, 4), (1, 3, 12), (3, 2, 9), (2, 4, 14), (4, 3, 7), (3, 5, 20), (4, 5, 4)] {
+            let edge = graph.add_edge(src, dst, String::new(), Properties::new());
+            graph.set_edge_property(edge, "capacity", capacity as f64);
+        }
+        let capacity = |i: usize, _: &_| graph.edge_float(i, "capacity").unwrap();
+        let nodes = graph.nodes().collect::<Vec<_>>();
+
+        for flow in [graph.dinic(nodes[0], nodes[5], capacity).unwrap(), graph.push_relabel(nodes[0], nodes[5], capacity).unwrap()] {
+            assert!((flow.value - 23.0).abs() < 1e-9);
+            let mut balance = [0.0; 6];
+            for (i, edge) in graph.edges().enumerate() {
+                let (u, v) = edge.pair();
+                assert!(flow.edge_flows[i] >= -1e-9 && flow.edge_flows[i] <= capacity(i, edge) + 1e-9);
+                balance[u] -= flow.edge_flows[i];
+                balance[v] += flow.edge_flows[i];
+            }
+            assert!(balance[1..5].iter().all(|b| b.abs() < 1e-9));
+            assert_eq!(flow.source_side.iter().map(|node| node.id()).collect::<Vec<_>>(), vec![0, 1, 2, 4]);
+        }
+    }
+
+    #[test]
+    fn unknown_terminals_and_dangling_edges() {
+        let mut graph = StandardLabeledGraph::new();
+        let mut other = StandardLabeledGraph::new();
+        for id in 0..3 {
+            graph.add_node(id, String::new());
+        }
+        other.add_node(7, String::new());
+        for (src, dst) in [(0, 1), (1, 9), (1, 2)] {
+            graph.add_edge(src, dst);
+        }
+        let nodes = graph.nodes().collect::<Vec<_>>();
+        let stranger = other.nodes().next().unwrap();
+
+        for flow in [graph.dinic(nodes[0], nodes[2], |_, _| 1.0), graph.push_relabel(nodes[0], nodes[2], |_, _| 1.0)] {
+            let flow = flow.unwrap();
+            assert_eq!((flow.value, flow.edge_flows), (1.0, vec![1.0, 0.0, 1.0]));
+        }
+        let flow = graph.dinic(nodes[1], nodes[1], |_, _| 1.0).unwrap();
+        assert_eq!((flow.value, flow.edge_flows), (0.0, vec![0.0; 3]));
+        assert!(graph.dinic(nodes[0], stranger, |_, _| 1.0).is_none());
+        assert!(graph.push_relabel(stranger, nodes[2], |_, _| 1.0).is_none());
+    }
+
+    #[test]
+    fn stoer_wagner_finds_the_bridge() {
+        // Two triangles joined by a single light edge, and an edge to a missing node.
+        let mut graph = StandardUnDirectedGraph::new();
+        for id in 0..6 {
+            graph.add_node(id, String::new());
+        }
+        for (src, dst) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+            graph.add_edge(src, dst, SingleLabel::default());
+        }
+        graph.add_edge(5, 9, SingleLabel::default());
+        let cut = graph.stoer_wagner(|_, _| 1.0).unwrap();
+        assert_eq!(cut.value, 1.0);
+        let mut side = cut.side.iter().map(|node| node.id()).collect::<Vec<_>>();
+        side.sort();
+        assert!(side == vec![0, 1, 2] || side == vec![3, 4, 5]);
+    }
+}
@@ -0,0 +1,496 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::interfaces::graph::{Graph, IdPair, SingleId};
+
+const NONE: usize = usize::MAX;
+
+/// The outcome of a bipartiteness check: the two sides, or a cycle of odd length proving there are none.
+#[derive(Clone, Debug)]
+pub enum Bipartition<'a, N> {
+    Bipartite { left: Vec<&'a N>, right: Vec<&'a N> },
+    /// Consecutive nodes are adjacent, as are the last and the first. A self-loop gives a single node.
+    OddCycle(Vec<&'a N>),
+}
+
+/// A minimum-cost assignment: `pairs` hold `(left, right)` nodes and `cost` their total cost.
+#[derive(Clone, Debug)]
+pub struct Assignment<'a, N> {
+    pub cost: f64,
+    pub pairs: Vec<(&'a N, &'a N)>,
+}
+
+// Neighbour positions of the undirected graph underneath, without repeats or edges with an endpoint that is not a node;
+// self-loops are kept apart.
+fn undirected<'a, G: Graph<'a>>(graph: &'a G) -> (Vec<&'a G::Node>, Vec<Vec<usize>>, Option<usize>) {
+    let nodes = graph.nodes().collect::<Vec<_>>();
+    let index = nodes.iter().enumerate().map(|(i, node)| (node.id(), i)).collect::<HashMap<_, _>>();
+    let mut neighbours = vec![Vec::new(); nodes.len()];
+    let mut self_loop = None;
+    for edge in graph.edges() {
+        let (src, dst) = edge.pair();
+        let (Some(&u), Some(&v)) = (index.get(&src), index.get(&dst)) else {
+            continue;
+        };
+        if u == v {
+            self_loop.get_or_insert(u);
+        } else {
+            neighbours[u].push(v);
+            neighbours[v].push(u);
+        }
+    }
+    for row in neighbours.iter_mut() {
+        row.sort_unstable();
+        row.dedup();
+    }
+    (nodes, neighbours, self_loop)
+}
+
+// Two-colour every component by BFS from its first node; on a conflict, the odd cycle through the BFS tree.
+fn colour(neighbours: &[Vec<usize>], self_loop: Option<usize>) -> Result<Vec<bool>, Vec<usize>> {
+    if let Some(u) = self_loop {
+        return Err(vec![u]);
+    }
+    let n = neighbours.len();
+    let (mut side, mut parent, mut depth) = (vec![false; n], vec![NONE; n], vec![NONE; n]);
+    for root in 0..n {
+        if depth[root] != NONE {
+            continue;
+        }
+        depth[root] = 0;
+        let mut queue = VecDeque::from([root]);
+        while let Some(u) = queue.pop_front() {
+            for &v in neighbours[u].iter() {
+                if depth[v] == NONE {
+                    (depth[v], parent[v], side[v]) = (depth[u] + 1, u, !side[u]);
+                    queue.push_back(v);
+                } else if side[v] == side[u] {
+                    // Climb from both ends to the common ancestor.
+                    let (mut a, mut b) = (u, v);
+                    let (mut up, mut down) = (Vec::new(), Vec::new());
+                    while depth[a] > depth[b] {
+                        up.push(a);
+                        a = parent[a];
+                    }
+                    while depth[b] > depth[a] {
+                        down.push(b);
+                        b = parent[b];
+                    }
+                    while a != b {
+                        up.push(a);
+                        down.push(b);
+                        (a, b) = (parent[a], parent[b]);
+                    }
+                    up.push(a);
+                    up.extend(down.into_iter().rev());
+                    return Err(up);
+                }
+            }
+        }
+    }
+    Ok(side)
+}
+
+/// Kuhn-Munkres on a cost matrix with one row per worker and one column per job; `f64::INFINITY` forbids a pair.
+/// Every row is assigned when there are at most as many rows as columns, every column otherwise. Returns the total
+/// cost and the column of each row, or `None` if forbidden pairs leave no full assignment.
+pub fn hungarian(costs: &[Vec<f64>]) -> Option<(f64, Vec<Option<usize>>)> {
+    let rows = costs.len();
+    let cols = costs.first().map_or(0, |row| row.len());
+    if rows > cols {
+        let transposed = (0..cols).map(|j| costs.iter().map(|row| row[j]).collect()).collect::<Vec<Vec<f64>>>();
+        let (cost, by_col) = hungarian(&transposed)?;
+        let mut by_row = vec![None; rows];
+        for (j, i) in by_col.into_iter().enumerate() {
+            by_row[i.unwrap()] = Some(j);
+        }
+        return Some((cost, by_row));
+    }
+    // Potentials `u`, `v` over 1-based rows and columns; column 0 is a virtual start.
+    let (mut u, mut v) = (vec![0.0; rows + 1], vec![0.0; cols + 1]);
+    let mut owner = vec![0; cols + 1];
+    for i in 1..=rows {
+        owner[0] = i;
+        let mut j0 = 0;
+        let mut slack = vec![f64::INFINITY; cols + 1];
+        let mut way = vec![0; cols + 1];
+        let mut used = vec![false; cols + 1];
+        loop {
+            used[j0] = true;
+            let i0 = owner[j0];
+            let (mut delta, mut j1) = (f64::INFINITY, 0);
+            for j in 1..=cols {
+                if !used[j] {
+                    let reduced = costs[i0 - 1][j - 1] - u[i0] - v[j];
+                    if reduced < slack[j] {
+                        slack[j] = reduced;
+                        way[j] = j0;
+                    }
+                    if slack[j] < delta {
+                        delta = slack[j];
+                        j1 = j;
+                    }
+                }
+            }
+            if delta == f64::INFINITY {
+                return None;
+            }
+            for j in 0..=cols {
+                if used[j] {
+                    u[owner[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    slack[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if owner[j0] == 0 {
+                break;
+            }
+        }
+        while j0 != 0 {
+            let j1 = way[j0];
+            owner[j0] = owner[j1];
+            j0 = j1;
+        }
+    }
+    let mut assignment = vec![None; rows];
+    for j in 1..=cols {
+        if owner[j] != 0 {
+            assignment[owner[j] - 1] = Some(j - 1);
+        }
+    }
+    let cost = assignment.iter().enumerate().map(|(i, j)| costs[i][j.unwrap()]).sum();
+    Some((cost, assignment))
+}
+
+// Maximum cardinality matching by Edmonds' blossom algorithm; `mate[u]` is `NONE` for unmatched nodes.
+struct Blossom<'n> {
+    neighbours: &'n [Vec<usize>],
+    mate: Vec<usize>,
+    parent: Vec<usize>,
+    base: Vec<usize>,
+    used: Vec<bool>,
+    in_blossom: Vec<bool>,
+}
+
+impl Blossom<'_> {
+    fn lca(&self, mut a: usize, mut b: usize) -> usize {
+        let mut seen = vec![false; self.mate.len()];
+        loop {
+            a = self.base[a];
+            seen[a] = true;
+            if self.mate[a] == NONE {
+                break;
+            }
+            a = self.parent[self.mate[a]];
+        }
+        loop {
+            b = self.base[b];
+            if seen[b] {
+                return b;
+            }
+            b = self.parent[self.mate[b]];
+        }
+    }
+
+    fn mark_path(&mut self, mut v: usize, b: usize, mut child: usize) {
+        while self.base[v] != b {
+            self.in_blossom[self.base[v]] = true;
+            self.in_blossom[self.base[self.mate[v]]] = true;
+            self.parent[v] = child;
+            child = self.mate[v];
+            v = self.parent[self.mate[v]];
+        }
+    }
+
+    // BFS over alternating paths from `root`, contracting blossoms; the free node reached, if any.
+    fn find_path(&mut self, root: usize) -> usize {
+        let n = self.mate.len();
+        self.used.iter_mut().for_each(|x| *x = false);
+        self.parent.iter_mut().for_each(|x| *x = NONE);
+        self.base.iter_mut().enumerate().for_each(|(i, x)| *x = i);
+        self.used[root] = true;
+        let mut queue = VecDeque::from([root]);
+        while let Some(v) = queue.pop_front() {
+            for &to in self.neighbours[v].iter() {
+                if self.base[v] == self.base[to] || self.mate[v] == to {
+                    continue;
+                }
+                if to == root || self.mate[to] != NONE && self.parent[self.mate[to]] != NONE {
+                    let current = self.lca(v, to);
+                    self.in_blossom.iter_mut().for_each(|x| *x = false);
+                    self.mark_path(v, current, to);
+                    self.mark_path(to, current, v);
+                    for i in 0..n {
+                        if self.in_blossom[self.base[i]] {
+                            self.base[i] = current;
+                            if !self.used[i] {
+                                self.used[i] = true;
+                                queue.push_back(i);
+                            }
+                        }
+                    }
+                } else if self.parent[to] == NONE {
+                    self.parent[to] = v;
+                    if self.mate[to] == NONE {
+                        return to;
+                    }
+                    self.used[self.mate[to]] = true;
+                    queue.push_back(self.mate[to]);
+                }
+            }
+        }
+        NONE
+    }
+
+    fn solve(neighbours: &[Vec<usize>]) -> Vec<usize> {
+        let n = neighbours.len();
+        let mut blossom = Blossom {
+            neighbours,
+            mate: vec![NONE; n],
+            parent: vec![NONE; n],
+            base: (0..n).collect(),
+            used: vec![false; n],
+            in_blossom: vec![false; n],
+        };
+        // A greedy start leaves fewer searches.
+        for (u, row) in neighbours.iter().enumerate() {
+            if blossom.mate[u] == NONE {
+                if let Some(&v) = row.iter().find(|&&v| blossom.mate[v] == NONE) {
+                    blossom.mate[u] = v;
+                    blossom.mate[v] = u;
+                }
+            }
+        }
+        for root in 0..n {
+            if blossom.mate[root] != NONE {
+                continue;
+            }
+            let mut v = blossom.find_path(root);
+            while v != NONE {
+                let pv = blossom.parent[v];
+                let next = blossom.mate[pv];
+                blossom.mate[v] = pv;
+                blossom.mate[pv] = v;
+                v = next;
+            }
+        }
+        blossom.mate
+    }
+}
+
+// Hopcroft-Karp over `left` positions; returns the mate of every node.
+fn hopcroft_karp(neighbours: &[Vec<usize>], left: &[bool]) -> Vec<usize> {
+    let n = neighbours.len();
+    let mut mate = vec![NONE; n];
+    let lefts = (0..n).filter(|&u| left[u]).collect::<Vec<_>>();
+    loop {
+        // Layer the free left nodes and everything reachable from them by alternating paths.
+        let mut dist = vec![NONE; n];
+        let mut queue = lefts.iter().copied().filter(|&u| mate[u] == NONE).collect::<VecDeque<_>>();
+        queue.iter().for_each(|&u| dist[u] = 0);
+        let mut found = NONE;
+        while let Some(u) = queue.pop_front() {
+            if dist[u] >= found {
+                continue;
+            }
+            for &v in neighbours[u].iter() {
+                let w = mate[v];
+                if w == NONE {
+                    found = found.min(dist[u] + 1);
+                } else if dist[w] == NONE {
+                    dist[w] = dist[u] + 1;
+                    queue.push_back(w);
+                }
+            }
+        }
+        if found == NONE {
+            return mate;
+        }
+        // Vertex-disjoint shortest augmenting paths, by an explicit-stack DFS.
+        let mut next = vec![0; n];
+        for &root in lefts.iter() {
+            if mate[root] != NONE {
+                continue;
+            }
+            let (mut stack, mut via) = (vec![root], Vec::new());
+            while let Some(&x) = stack.last() {
+                if next[x] == neighbours[x].len() {
+                    dist[x] = NONE;
+                    stack.pop();
+                    via.pop();
+                    continue;
+                }
+                let v = neighbours[x][next[x]];
+                next[x] += 1;
+                let w = mate[v];
+                if w == NONE && dist[x] + 1 == found {
+                    via.push(v);
+                    for (&l, &r) in stack.iter().zip(via.iter()) {
+                        mate[l] = r;
+                        mate[r] = l;
+                    }
+                    break;
+                } else if w != NONE && dist[w] == dist[x] + 1 {
+                    via.push(v);
+                    stack.push(w);
+                }
+            }
+        }
+    }
+}
+
+// The side of every node from the caller's `left` predicate, or `None` if some edge does not cross between the sides.
+fn sides<N>(nodes: &[&N], neighbours: &[Vec<usize>], self_loop: Option<usize>, left: impl Fn(&N) -> bool) -> Option<Vec<bool>> {
+    if self_loop.is_some() {
+        return None;
+    }
+    let left = nodes.iter().map(|node| left(node)).collect::<Vec<_>>();
+    let crossing = (0..nodes.len()).all(|u| neighbours[u].iter().all(|&v| left[u] != left[v]));
+    crossing.then_some(left)
+}
+
+fn pairs<'a, N>(nodes: &[&'a N], mate: &[usize], first: impl Fn(usize) -> bool) -> Vec<(&'a N, &'a N)> {
+    (0..nodes.len()).filter(|&u| mate[u] != NONE && first(u)).map(|u| (nodes[u], nodes[mate[u]])).collect()
+}
+
+/// Matchings on a graph, with edges read as undirected. Bipartite methods take the left side as a predicate on nodes,
+/// e.g. `|node| node.type_id() == WORKER`, and every edge must join a left node to a right one.
+pub trait Matching<'a>: Graph<'a> + Sized {
+    /// Two sides found by colouring, with the first node of each connected component on the left, or an odd cycle.
+    fn bipartition(&'a self) -> Bipartition<'a, Self::Node> {
+        let (nodes, neighbours, self_loop) = undirected(self);
+        match colour(&neighbours, self_loop) {
+            Ok(side) => {
+                let (right, left) = nodes.into_iter().zip(side).partition::<Vec<_>, _>(|&(_, s)| s);
+                Bipartition::Bipartite {
+                    left: left.into_iter().map(|(node, _)| node).collect(),
+                    right: right.into_iter().map(|(node, _)| node).collect(),
+                }
+            }
+            Err(cycle) => Bipartition::OddCycle(cycle.into_iter().map(|u| nodes[u]).collect()),
+        }
+    }
+
+    /// A maximum matching as `(left, right)` pairs; `None` if an edge does not cross between the sides.
+    fn hopcroft_karp(&'a self, left: impl Fn(&Self::Node) -> bool) -> Option<Vec<(&'a Self::Node, &'a Self::Node)>> {
+        let (nodes, neighbours, self_loop) = undirected(self);
+        let left = sides(&nodes, &neighbours, self_loop, left)?;
+        let mate = hopcroft_karp(&neighbours, &left);
+        Some(pairs(&nodes, &mate, |u| left[u]))
+    }
+
+    /// A minimum-cost assignment of the `left` nodes (rows) to the others (columns) by `hungarian`, through edges only:
+    /// every left node is covered when there are at most as many of them as right nodes, every right node otherwise.
+    /// `cost` is read like the capacities of `Flow`; parallel edges keep the cheapest. `None` if an edge does not cross
+    /// between the sides or the smaller side cannot be covered. Edges with an endpoint that is not a node are skipped.
+    fn assignment(&'a self, left: impl Fn(&Self::Node) -> bool, cost: impl Fn(usize, &Self::Edge) -> f64) -> Option<Assignment<'a, Self::Node>> {
+        let (nodes, neighbours, self_loop) = undirected(self);
+        let left = sides(&nodes, &neighbours, self_loop, left)?;
+        let (mut row, mut col) = (vec![NONE; nodes.len()], vec![NONE; nodes.len()]);
+        let (mut rows, mut cols) = (Vec::new(), Vec::new());
+        for u in 0..nodes.len() {
+            if left[u] {
+                row[u] = rows.len();
+                rows.push(u);
+            } else {
+                col[u] = cols.len();
+                cols.push(u);
+            }
+        }
+        let mut costs = vec![vec![f64::INFINITY; cols.len()]; rows.len()];
+        let index = nodes.iter().enumerate().map(|(i, node)| (node.id(), i)).collect::<HashMap<_, _>>();
+        for (i, edge) in self.edges().enumerate() {
+            let (src, dst) = edge.pair();
+            let (Some(&u), Some(&v)) = (index.get(&src), index.get(&dst)) else {
+                continue;
+            };
+            let (l, r) = if left[u] { (u, v) } else { (v, u) };
+            let entry = &mut costs[row[l]][col[r]];
+            *entry = entry.min(cost(i, edge));
+        }
+        let (total, assigned) = hungarian(&costs)?;
+        let pairs = assigned.into_iter().enumerate().filter_map(|(i, j)| j.map(|j| (nodes[rows[i]], nodes[cols[j]]))).collect();
+        Some(Assignment { cost: total, pairs })
+    }
+
+    /// A maximum matching of any graph by Edmonds' blossom algorithm. Each pair appears once, in graph order.
+    fn blossom(&'a self) -> Vec<(&'a Self::Node, &'a Self::Node)> {
+        let (nodes, neighbours, _) = undirected(self);
+        let mate = Blossom::solve(&neighbours);
+        pairs(&nodes, &mate, |u| u < mate[u])
+    }
+}
+
+impl<'a, G: Graph<'a>> Matching<'a> for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::standard::{LabelNode, SingleLabel, StandardUnDirectedGraph};
+
+    fn graph(n: u64, edges: &[(u64, u64)]) -> StandardUnDirectedGraph {
+        let mut graph = StandardUnDirectedGraph::new();
+        for id in 0..n {
+            graph.add_node(id, String::new());
+        }
+        for &(src, dst) in edges {
+            graph.add_edge(src, dst, SingleLabel::default());
+        }
+        graph
+    }
+
+    #[test]
+    fn bipartite_matchings() {
+        // Left 0..3, right 3..6; 0 and 1 both only like 3.
+        let g = graph(6, &[(0, 3), (1, 3), (2, 3), (2, 4), (2, 5)]);
+        assert_eq!(g.hopcroft_karp(|node| node.id() < 3).unwrap().len(), 2);
+        assert_eq!(g.blossom().len(), 2);
+
+        let h = graph(4, &[(0, 2), (0, 3), (1, 2), (1, 3)]);
+        let costs = [4.0, 1.0, 2.0, 5.0];
+        let assignment = h.assignment(|node| node.id() < 2, |i, _| costs[i]).unwrap();
+        assert_eq!(assignment.cost, 3.0);
+        let pairs = assignment.pairs.iter().map(|(l, r)| (l.id(), r.id())).collect::<Vec<_>>();
+        assert_eq!(pairs, vec![(0, 3), (1, 2)]);
+
+        let (cost, assigned) = hungarian(&[vec![3.0, 1.0], vec![2.0, 2.0], vec![1.0, 5.0]]).unwrap();
+        assert_eq!((cost, assigned), (2.0, vec![Some(1), None, Some(0)]));
+    }
+
+    #[test]
+    fn odd_cycles_and_blossoms() {
+        // A 5-cycle with a pendant node: not bipartite, but perfectly matchable through the blossom.
+        let g = graph(6, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (0, 5)]);
+        match g.bipartition() {
+            Bipartition::OddCycle(cycle) => assert_eq!(cycle.len(), 5),
+            Bipartition::Bipartite { .. } => panic!("a 5-cycle is not bipartite"),
+        }
+        assert!(g.hopcroft_karp(|node| node.id() % 2 == 0).is_none());
+        assert_eq!(g.blossom().len(), 3);
+    }
+
+    #[test]
+    fn sides_are_chosen_by_the_caller() {
+        // Workers 0, 1, 6 and 7; jobs 2, 3, 4, 5 and 8. In the second component a job comes first. The last edge leads
+        // to a missing node.
+        let edges = [(0, 2), (0, 3), (1, 2), (1, 3), (4, 6), (5, 6), (4, 7), (5, 7), (8, 6), (7, 9)];
+        let g = graph(9, &edges);
+        let worker = |node: &LabelNode<String>| [0, 1, 6, 7].contains(&node.id());
+
+        let matching = g.hopcroft_karp(worker).unwrap();
+        assert_eq!(matching.len(), 4);
+        assert!(matching.iter().all(|(l, r)| worker(l) && !worker(r)));
+        assert_eq!(g.blossom().len(), 4);
+
+        let costs = [1.0, 4.0, 3.0, 1.0, 2.0, 5.0, 6.0, 1.0, 1.0, 0.0];
+        let assignment = g.assignment(worker, |i, _| costs[i]).unwrap();
+        assert_eq!(assignment.cost, 4.0);
+        let pairs = assignment.pairs.iter().map(|(l, r)| (l.id(), r.id())).collect::<Vec<_>>();
+        assert_eq!(pairs, vec![(0, 2), (1, 3), (6, 8), (7, 5)]);
+
+        // Workers 0 and 1 with jobs 2 and 3 on the same side leave edges inside it.
+        assert!(g.hopcroft_karp(|node| node.id() < 4).is_none());
+        assert!(g.assignment(|node| node.id() < 4, |i, _| costs[i]).is_none());
+    }
+}
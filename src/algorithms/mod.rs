@@ -0,0 +1,9 @@
+pub mod reduction;
+pub mod transversal;
+pub mod partition;
+pub mod community;
+pub mod centrality;
+pub mod structure;
+pub mod flow;
+pub mod matching;
+pub mod spanning;
\ No newline at end of file
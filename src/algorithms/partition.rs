@@ -0,0 +1,544 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::interfaces::edge::{Hyperedge, Weighted};
+use crate::interfaces::graph::SingleId;
+use crate::interfaces::hypergraph::Hypergraph;
+
+/// The quantity a partition minimises.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CutMetric {
+    /// Total weight of the hyperedges spanning more than one part.
+    CutNet,
+    /// Sum over hyperedges of weight times (parts spanned - 1).
+    Connectivity,
+}
+
+#[derive(Clone, Debug)]
+pub struct PartitionConfig {
+    pub k: usize,
+    /// Every part weighs at most `(1 + imbalance) * total / k`.
+    pub imbalance: f64,
+    pub metric: CutMetric,
+    /// Coarsening stops once a level has at most this many nodes.
+    pub coarsen_to: usize,
+    /// Initial partitions tried on the coarsest level; the best one is kept.
+    pub initial_tries: usize,
+    /// Upper bound on FM passes per level.
+    pub refinement_passes: usize,
+    pub seed: u64,
+}
+
+impl PartitionConfig {
+    pub fn new(k: usize) -> Self {
+        PartitionConfig {
+            k,
+            imbalance: 0.03,
+            metric: CutMetric::Connectivity,
+            coarsen_to: (20 * k).max(100),
+            initial_tries: 8,
+            refinement_passes: 8,
+            seed: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Partition {
+    /// The part of every node, by node id.
+    pub parts: HashMap<usize, usize>,
+    pub part_weights: Vec<f64>,
+    /// The value of the configured metric.
+    pub objective: f64,
+}
+
+impl Partition {
+    /// How much the heaviest part exceeds the average part weight, e.g. `0.03` for 3%.
+    pub fn imbalance(&self) -> f64 {
+        let total = self.part_weights.iter().sum::<f64>();
+        let max = self.part_weights.iter().copied().fold(0.0, f64::max);
+        if total == 0.0 { 0.0 } else { max / (total / self.part_weights.len() as f64) - 1.0 }
+    }
+}
+
+// One level of the multilevel hierarchy, over nodes `0..node_w.len()`. Hyperedges have at least two distinct pins.
+struct Level {
+    node_w: Vec<f64>,
+    edges: Vec<Vec<usize>>,
+    edge_w: Vec<f64>,
+    incident: Vec<Vec<usize>>,
+}
+
+impl Level {
+    fn new(node_w: Vec<f64>, edges: Vec<Vec<usize>>, edge_w: Vec<f64>) -> Self {
+        let mut incident = vec![Vec::new(); node_w.len()];
+        for (e, pins) in edges.iter().enumerate() {
+            for &v in pins {
+                incident[v].push(e);
+            }
+        }
+        Level { node_w, edges, edge_w, incident }
+    }
+
+    fn len(&self) -> usize {
+        self.node_w.len()
+    }
+}
+
+// Hyperedges this large are skipped when rating matches: they say little about which pair of nodes belongs together.
+const MAX_RATED_EDGE: usize = 1000;
+
+// Heavy-edge matching: every node is paired with the unmatched neighbour it shares the most weight per pin with.
+// Returns the coarse level and the coarse node of every fine node.
+fn coarsen(level: &Level, max_node_w: f64, rng: &mut StdRng) -> (Level, Vec<usize>) {
+    let n = level.len();
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.shuffle(rng);
+    let mut mate = vec![usize::MAX; n];
+    let mut score = vec![0.0; n];
+    let mut touched = Vec::new();
+    for &u in order.iter() {
+        if mate[u] != usize::MAX {
+            continue;
+        }
+        for &e in level.incident[u].iter() {
+            let pins = &level.edges[e];
+            if pins.len() > MAX_RATED_EDGE {
+                continue;
+            }
+            let rating = level.edge_w[e] / (pins.len() - 1) as f64;
+            for &v in pins {
+                if v != u && mate[v] == usize::MAX {
+                    if score[v] == 0.0 {
+                        touched.push(v);
+                    }
+                    score[v] += rating;
+                }
+            }
+        }
+        let best = touched.iter().copied()
+            .filter(|&v| level.node_w[u] + level.node_w[v] <= max_node_w)
+            .max_by(|&a, &b| score[a].total_cmp(&score[b]).then(b.cmp(&a)));
+        for v in touched.drain(..) {
+            score[v] = 0.0;
+        }
+        match best {
+            Some(v) => { mate[u] = v; mate[v] = u; }
+            None => mate[u] = u,
+        }
+    }
+
+    let mut map = vec![usize::MAX; n];
+    let mut node_w = Vec::new();
+    for u in 0..n {
+        if map[u] == usize::MAX {
+            map[u] = node_w.len();
+            map[mate[u]] = node_w.len();
+            node_w.push(level.node_w[u] + if mate[u] != u { level.node_w[mate[u]] } else { 0.0 });
+        }
+    }
+
+    // Parallel hyperedges are merged and hyperedges left with a single pin dropped, since they can never be cut.
+    let mut merged: HashMap<Vec<usize>, usize> = HashMap::new();
+    let (mut edges, mut edge_w) = (Vec::new(), Vec::new());
+    for (e, pins) in level.edges.iter().enumerate() {
+        let mut coarse = pins.iter().map(|&v| map[v]).collect::<Vec<_>>();
+        coarse.sort_unstable();
+        coarse.dedup();
+        if coarse.len() < 2 {
+            continue;
+        }
+        match merged.get(&coarse) {
+            Some(&i) => edge_w[i] += level.edge_w[e],
+            None => {
+                merged.insert(coarse.clone(), edges.len());
+                edges.push(coarse);
+                edge_w.push(level.edge_w[e]);
+            }
+        }
+    }
+    (Level::new(node_w, edges, edge_w), map)
+}
+
+#[derive(PartialEq)]
+struct Move {
+    gain: f64,
+    node: usize,
+    to: usize,
+    stamp: usize,
+}
+
+impl Eq for Move {}
+
+impl PartialOrd for Move {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Move {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.gain.total_cmp(&other.gain).then(other.node.cmp(&self.node))
+    }
+}
+
+// A k-way assignment of a level with the pin count of every hyperedge in every part.
+struct State<'l> {
+    level: &'l Level,
+    k: usize,
+    metric: CutMetric,
+    max_part_w: f64,
+    part: Vec<usize>,
+    part_w: Vec<f64>,
+    pins: Vec<usize>,
+}
+
+impl<'l> State<'l> {
+    fn new(level: &'l Level, part: Vec<usize>, config: &PartitionConfig, max_part_w: f64) -> Self {
+        let k = config.k;
+        let mut part_w = vec![0.0; k];
+        for (v, &p) in part.iter().enumerate() {
+            part_w[p] += level.node_w[v];
+        }
+        let mut pins = vec![0; level.edges.len() * k];
+        for (e, edge) in level.edges.iter().enumerate() {
+            for &v in edge {
+                pins[e * k + part[v]] += 1;
+            }
+        }
+        State { level, k, metric: config.metric, max_part_w, part, part_w, pins }
+    }
+
+    fn spanned(&self, e: usize) -> usize {
+        self.pins[e * self.k..(e + 1) * self.k].iter().filter(|&&c| c > 0).count()
+    }
+
+    fn objective(&self) -> f64 {
+        (0..self.level.edges.len()).map(|e| {
+            let lambda = self.spanned(e);
+            let w = self.level.edge_w[e];
+            match self.metric {
+                CutMetric::CutNet => if lambda > 1 { w } else { 0.0 },
+                CutMetric::Connectivity => w * (lambda - 1) as f64,
+            }
+        }).sum()
+    }
+
+    fn gain(&self, v: usize, to: usize) -> f64 {
+        let from = self.part[v];
+        self.level.incident[v].iter().map(|&e| {
+            let (w, size) = (self.level.edge_w[e], self.level.edges[e].len());
+            let (in_from, in_to) = (self.pins[e * self.k + from], self.pins[e * self.k + to]);
+            match self.metric {
+                CutMetric::Connectivity => (if in_from == 1 { w } else { 0.0 }) - (if in_to == 0 { w } else { 0.0 }),
+                CutMetric::CutNet => (if in_to == size - 1 { w } else { 0.0 }) - (if in_from == size { w } else { 0.0 }),
+            }
+        }).sum()
+    }
+
+    // The best move of `v` to a part it shares a hyperedge with that has room for it.
+    fn best_move(&self, v: usize) -> Option<(f64, usize)> {
+        let from = self.part[v];
+        let mut best: Option<(f64, usize)> = None;
+        let mut seen = vec![false; self.k];
+        for &e in self.level.incident[v].iter() {
+            for (to, &count) in self.pins[e * self.k..(e + 1) * self.k].iter().enumerate() {
+                if to == from || seen[to] || count == 0 {
+                    continue;
+                }
+                seen[to] = true;
+                if self.part_w[to] + self.level.node_w[v] > self.max_part_w {
+                    continue;
+                }
+                let gain = self.gain(v, to);
+                if best.is_none_or(|(g, _)| gain > g) {
+                    best = Some((gain, to));
+                }
+            }
+        }
+        best
+    }
+
+    fn apply(&mut self, v: usize, to: usize) {
+        let from = self.part[v];
+        for &e in self.level.incident[v].iter() {
+            self.pins[e * self.k + from] -= 1;
+            self.pins[e * self.k + to] += 1;
+        }
+        self.part_w[from] -= self.level.node_w[v];
+        self.part_w[to] += self.level.node_w[v];
+        self.part[v] = to;
+    }
+
+    // Move nodes out of overweight parts, best gain first, until every part fits or no move helps.
+    fn rebalance(&mut self) {
+        while let Some(heavy) = (0..self.k).filter(|&p| self.part_w[p] > self.max_part_w).max_by(|&a, &b| self.part_w[a].total_cmp(&self.part_w[b])) {
+            let mut best: Option<(f64, usize, usize)> = None;
+            for v in (0..self.level.len()).filter(|&v| self.part[v] == heavy) {
+                for to in (0..self.k).filter(|&to| to != heavy && self.part_w[to] + self.level.node_w[v] <= self.max_part_w) {
+                    let gain = self.gain(v, to);
+                    if best.is_none_or(|(g, _, _)| gain > g) {
+                        best = Some((gain, v, to));
+                    }
+                }
+            }
+            match best {
+                Some((_, v, to)) => self.apply(v, to),
+                None => break,
+            }
+        }
+    }
+
+    // One Fiduccia-Mattheyses pass: move every node at most once, best gain first, also through worse states,
+    // then roll back to the best state seen. Returns whether the objective improved.
+    fn fm_pass(&mut self) -> bool {
+        let n = self.level.len();
+        let mut heap = BinaryHeap::new();
+        let mut stamps = vec![0; n];
+        for v in 0..n {
+            if self.level.incident[v].iter().any(|&e| self.spanned(e) > 1) {
+                if let Some((gain, to)) = self.best_move(v) {
+                    heap.push(Move { gain, node: v, to, stamp: 0 });
+                }
+            }
+        }
+        let mut locked = vec![false; n];
+        let mut moves = Vec::new();
+        let (mut current, mut best, mut best_len) = (0.0, 0.0, 0);
+        let limit = 50.max(n / 10);
+        while let Some(m) = heap.pop() {
+            if locked[m.node] || m.stamp != stamps[m.node] {
+                continue;
+            }
+            match self.best_move(m.node) {
+                Some((gain, to)) if gain == m.gain && to == m.to => {}
+                Some((gain, to)) => {
+                    stamps[m.node] += 1;
+                    heap.push(Move { gain, node: m.node, to, stamp: stamps[m.node] });
+                    continue;
+                }
+                None => continue,
+            }
+            moves.push((m.node, self.part[m.node]));
+            self.apply(m.node, m.to);
+            locked[m.node] = true;
+            current -= m.gain;
+            if current < best - 1e-9 {
+                best = current;
+                best_len = moves.len();
+            } else if moves.len() - best_len > limit {
+                break;
+            }
+            for &e in self.level.incident[m.node].iter() {
+                for &u in self.level.edges[e].iter() {
+                    if !locked[u] {
+                        stamps[u] += 1;
+                        if let Some((gain, to)) = self.best_move(u) {
+                            heap.push(Move { gain, node: u, to, stamp: stamps[u] });
+                        }
+                    }
+                }
+            }
+        }
+        for &(v, from) in moves[best_len..].iter().rev() {
+            self.apply(v, from);
+        }
+        best_len > 0
+    }
+
+    fn refine(&mut self, passes: usize) {
+        self.rebalance();
+        for _ in 0..passes {
+            if !self.fm_pass() {
+                break;
+            }
+        }
+    }
+}
+
+// Grow parts one after another in breadth-first order from random seeds, each up to an equal share of the weight.
+fn grow(level: &Level, k: usize, rng: &mut StdRng) -> Vec<usize> {
+    let n = level.len();
+    let target = level.node_w.iter().sum::<f64>() / k as f64;
+    let mut part = vec![usize::MAX; n];
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.shuffle(rng);
+    let (mut current, mut weight) = (0, 0.0);
+    let mut queue = VecDeque::new();
+    for &seed in order.iter() {
+        if part[seed] != usize::MAX {
+            continue;
+        }
+        queue.push_back(seed);
+        while let Some(v) = queue.pop_front() {
+            if part[v] != usize::MAX {
+                continue;
+            }
+            if weight + level.node_w[v] / 2.0 > target && current + 1 < k {
+                current += 1;
+                weight = 0.0;
+            }
+            part[v] = current;
+            weight += level.node_w[v];
+            for &e in level.incident[v].iter() {
+                queue.extend(level.edges[e].iter().copied().filter(|&u| part[u] == usize::MAX));
+            }
+        }
+    }
+    part
+}
+
+fn multilevel(finest: Level, config: &PartitionConfig) -> Vec<usize> {
+    let k = config.k;
+    let total = finest.node_w.iter().sum::<f64>();
+    let max_part_w = (1.0 + config.imbalance) * total / k as f64;
+    let mut rng = StdRng::seed_from_u64(config.seed);
+
+    let max_node_w = (1.5 * total / config.coarsen_to.max(k) as f64).max(finest.node_w.iter().copied().fold(0.0, f64::max));
+    let mut levels = vec![finest];
+    let mut maps: Vec<Vec<usize>> = Vec::new();
+    while levels.last().unwrap().len() > config.coarsen_to {
+        let (coarse, map) = coarsen(levels.last().unwrap(), max_node_w, &mut rng);
+        // Stop when matching no longer shrinks the hypergraph noticeably.
+        if coarse.len() as f64 > 0.95 * levels.last().unwrap().len() as f64 {
+            break;
+        }
+        levels.push(coarse);
+        maps.push(map);
+    }
+
+    let coarsest = levels.last().unwrap();
+    let mut best: Option<(bool, f64, Vec<usize>)> = None;
+    for _ in 0..config.initial_tries.max(1) {
+        let mut state = State::new(coarsest, grow(coarsest, k, &mut rng), config, max_part_w);
+        state.refine(config.refinement_passes);
+        let balanced = state.part_w.iter().all(|&w| w <= max_part_w);
+        let objective = state.objective();
+        if best.as_ref().is_none_or(|(b, o, _)| (balanced, -objective) > (*b, -*o)) {
+            best = Some((balanced, objective, state.part));
+        }
+    }
+    let mut part = best.unwrap().2;
+
+    for (level, map) in levels[..levels.len() - 1].iter().zip(maps.iter()).rev() {
+        let projected = map.iter().map(|&c| part[c]).collect();
+        let mut state = State::new(level, projected, config, max_part_w);
+        state.refine(config.refinement_passes);
+        part = state.part;
+    }
+    part
+}
+
+/// Multilevel k-way partitioning: heavy-edge coarsening, greedy growing on the coarsest level, and k-way
+/// Fiduccia-Mattheyses refinement while uncoarsening. Hyperedge weights come from `Weighted`.
+pub trait Partitioning<'a>: Hypergraph<'a> + Sized {
+    /// Partition the nodes into `config.k` parts. `node_weights`, in `nodes()` order, default to 1.
+    ///
+    /// Returns `None` if `config.k` is 0, if `node_weights` does not hold one weight per node, or if a hyperedge
+    /// refers to a node that is not in the hypergraph.
+    fn partition(&'a self, node_weights: Option<&[f64]>, config: &PartitionConfig) -> Option<Partition>
+    where Self::Edge: Weighted {
+        if config.k == 0 {
+            return None;
+        }
+        let ids = self.nodes().map(|node| node.id()).collect::<Vec<_>>();
+        let index = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect::<HashMap<_, _>>();
+        let node_w = match node_weights {
+            Some(w) if w.len() == ids.len() => w.to_vec(),
+            Some(_) => return None,
+            None => vec![1.0; ids.len()],
+        };
+        let (mut edges, mut edge_w) = (Vec::new(), Vec::new());
+        for edge in self.hyperedges() {
+            let pins = edge.sorted_ids().iter().map(|id| index.get(id).copied()).collect::<Option<Vec<_>>>()?;
+            if pins.len() > 1 {
+                edges.push(pins);
+                edge_w.push(edge.weight());
+            }
+        }
+
+        let level = Level::new(node_w, edges, edge_w);
+        let part = multilevel(level, config);
+        let parts = ids.iter().zip(part.iter()).map(|(&id, &p)| (id, p)).collect::<HashMap<_, _>>();
+        let objective = self.partition_cost(&parts, config.metric);
+        let mut part_weights = vec![0.0; config.k];
+        for (i, &p) in part.iter().enumerate() {
+            part_weights[p] += node_weights.map_or(1.0, |w| w[i]);
+        }
+        Some(Partition { parts, part_weights, objective })
+    }
+
+    /// The value of `metric` for a given assignment of node ids to parts. Members without a part are not counted.
+    fn partition_cost(&'a self, parts: &HashMap<usize, usize>, metric: CutMetric) -> f64
+    where Self::Edge: Weighted {
+        self.hyperedges().map(|edge| {
+            let mut spanned = edge.sorted_ids().iter().filter_map(|id| parts.get(id).copied()).collect::<Vec<_>>();
+            spanned.sort_unstable();
+            spanned.dedup();
+            let lambda = spanned.len().max(1);
+            match metric {
+                CutMetric::CutNet => if lambda > 1 { edge.weight() } else { 0.0 },
+                CutMetric::Connectivity => edge.weight() * (lambda - 1) as f64,
+            }
+        }).sum()
+    }
+}
+
+impl<'a, H: Hypergraph<'a>> Partitioning<'a> for H {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::hypergraph::{HyperedgeImpl, HypergraphImpl, Node};
+    use crate::interfaces::edge::NodeSet;
+    use rand::Rng;
+
+    #[test]
+    fn finds_planted_four_way_partition() {
+        // Four dense clusters of 50 nodes joined by a few light hyperedges.
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut hypergraph = HypergraphImpl::new();
+        for id in 0..200 {
+            hypergraph.add_node(Node::from_id(id));
+        }
+        for _ in 0..600 {
+            let cluster = rng.random_range(0..4) * 50;
+            let pins = (0..rng.random_range(2..5)).map(|_| cluster + rng.random_range(0..50)).collect();
+            hypergraph.add_hyperedge(HyperedgeImpl::from_nodes(pins));
+        }
+        for _ in 0..8 {
+            let mut edge = HyperedgeImpl::from_nodes(vec![rng.random_range(0..200), rng.random_range(0..200)]);
+            edge.set_weight(0.5);
+            hypergraph.add_hyperedge(edge);
+        }
+
+        let mut config = PartitionConfig::new(4);
+        config.coarsen_to = 40;
+        let partition = hypergraph.partition(None, &config).unwrap();
+        assert!(partition.imbalance() <= config.imbalance + 1e-9);
+        assert!(partition.objective <= 4.0, "objective {}", partition.objective);
+        assert_eq!(partition.objective, hypergraph.partition_cost(&partition.parts, CutMetric::Connectivity));
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        let mut hypergraph = HypergraphImpl::new();
+        for id in 0..4 {
+            hypergraph.add_node(Node::from_id(id));
+        }
+        hypergraph.add_hyperedge(HyperedgeImpl::from_nodes(vec![0, 1]));
+        hypergraph.add_hyperedge(HyperedgeImpl::from_nodes(vec![2, 3]));
+
+        assert!(hypergraph.partition(None, &PartitionConfig::new(0)).is_none());
+        assert!(hypergraph.partition(Some(&[1.0, 1.0]), &PartitionConfig::new(2)).is_none());
+        let partition = hypergraph.partition(Some(&[1.0; 4]), &PartitionConfig::new(2)).unwrap();
+        assert_eq!(partition.objective, 0.0);
+
+        hypergraph.add_hyperedge(HyperedgeImpl::from_nodes(vec![3, 9]));
+        assert!(hypergraph.partition(None, &PartitionConfig::new(2)).is_none());
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use crate::interfaces::edge::{Hyperedge, HyperedgeKind};
+use crate::interfaces::hypergraph::{Hypergraph, ToOwnedHypergraph};
+
+/// How the hyperedges of a hypergraph contain each other. Hyperedges are numbered by their position in `hyperedges()`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContainmentReport {
+    /// For each hyperedge, the first hyperedge equal to it (itself when it is the first).
+    pub representative: Vec<usize>,
+    /// For each representative, the smallest hyperedges strictly containing it. Empty for duplicates.
+    pub parents: Vec<Vec<usize>>,
+}
+
+impl ContainmentReport {
+    /// Groups of two or more equal hyperedges.
+    pub fn duplicates(&self) -> Vec<Vec<usize>> {
+        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (i, &rep) in self.representative.iter().enumerate() {
+            groups.entry(rep).or_default().push(i);
+        }
+        let mut groups = groups.into_values().filter(|group| group.len() > 1).collect::<Vec<_>>();
+        groups.sort();
+        groups
+    }
+
+    /// The representatives contained in no other hyperedge, i.e. the maximal faces.
+    pub fn maximal(&self) -> Vec<usize> {
+        self.representatives().filter(|&i| self.parents[i].is_empty()).collect()
+    }
+
+    /// Every maximal containment chain, from a hyperedge containing no other up to a maximal one.
+    /// A hyperedge that contains nothing and is contained in nothing forms a chain of its own.
+    pub fn chains(&self) -> Vec<Vec<usize>> {
+        let mut has_child = vec![false; self.parents.len()];
+        for parents in self.parents.iter() {
+            for &p in parents {
+                has_child[p] = true;
+            }
+        }
+        let mut chains = Vec::new();
+        for start in self.representatives().filter(|&i| !has_child[i]) {
+            let mut stack = vec![vec![start]];
+            while let Some(chain) = stack.pop() {
+                let last = *chain.last().unwrap();
+                if self.parents[last].is_empty() {
+                    chains.push(chain);
+                    continue;
+                }
+                for &p in self.parents[last].iter() {
+                    let mut longer = chain.clone();
+                    longer.push(p);
+                    stack.push(longer);
+                }
+            }
+        }
+        chains.sort();
+        chains
+    }
+
+    fn representatives(&self) -> impl Iterator<Item = usize> + '_ {
+        self.representative.iter().enumerate().filter(|(i, rep)| i == *rep).map(|(i, _)| i)
+    }
+}
+
+// Members in the form `is_equal` compares, so that equal hyperedges get equal keys.
+fn canonical<E: Hyperedge>(edge: &E) -> Vec<usize> {
+    match edge.kind() {
+        HyperedgeKind::Set => edge.sorted_ids().into_owned(),
+        HyperedgeKind::Multiset => edge.sorted_members().into_owned(),
+        HyperedgeKind::Ordered => edge.id(),
+    }
+}
+
+fn representatives<E: Hyperedge>(edges: &[&E]) -> Vec<usize> {
+    let mut first = HashMap::new();
+    edges.iter().enumerate().map(|(i, edge)| *first.entry(canonical(*edge)).or_insert(i)).collect()
+}
+
+/// Duplicate removal and subsumption reduction, available on every hypergraph.
+pub trait Reduction<'a>: Hypergraph<'a> + Sized {
+    fn containment_report(&'a self) -> ContainmentReport {
+        let edges = self.hyperedges().collect::<Vec<_>>();
+        let representative = representatives(&edges);
+        let distinct = (0..edges.len()).filter(|&i| representative[i] == i).collect::<Vec<_>>();
+
+        // Only hyperedges sharing a member can contain a non-empty one, so candidates come from its rarest member.
+        let mut incidence: HashMap<usize, Vec<usize>> = HashMap::new();
+        for &i in distinct.iter() {
+            for &id in edges[i].sorted_ids().iter() {
+                incidence.entry(id).or_default().push(i);
+            }
+        }
+        let mut parents = vec![Vec::new(); edges.len()];
+        for &i in distinct.iter() {
+            let ids = edges[i].sorted_ids();
+            let candidates = match ids.iter().min_by_key(|id| incidence[id].len()) {
+                Some(rarest) => &incidence[rarest],
+                None => &distinct,
+            };
+            let supersets = candidates.iter().copied().filter(|&j| j != i && edges[i].is_subset(edges[j])).collect::<Vec<_>>();
+            parents[i] = supersets.iter().copied().filter(|&j| {
+                !supersets.iter().any(|&k| k != j && edges[k].is_subset(edges[j]))
+            }).collect();
+        }
+        ContainmentReport { representative, parents }
+    }
+
+    /// A copy keeping only the first of each group of equal hyperedges.
+    fn deduplicated(&'a self) -> Self::Owned
+    where Self: ToOwnedHypergraph<'a> {
+        let edges = self.hyperedges().collect::<Vec<_>>();
+        let representative = representatives(&edges);
+        self.rebuild((0..edges.len()).filter(|&i| representative[i] == i).map(|i| edges[i]))
+    }
+
+    /// A copy keeping one copy of each maximal hyperedge, dropping every hyperedge contained in another.
+    fn reduced(&'a self) -> Self::Owned
+    where Self: ToOwnedHypergraph<'a> {
+        let edges = self.hyperedges().collect::<Vec<_>>();
+        self.rebuild(self.containment_report().maximal().into_iter().map(|i| edges[i]))
+    }
+
+    fn rebuild(&'a self, edges: impl Iterator<Item = &'a Self::Edge>) -> Self::Owned
+    where Self: ToOwnedHypergraph<'a> {
+        let mut hypergraph = Self::Owned::new();
+        for node in self.nodes() {
+            hypergraph.add_node(Self::to_owned_node(node));
+        }
+        for edge in edges {
+            hypergraph.add_hyperedge(Self::to_owned_edge(edge));
+        }
+        hypergraph
+    }
+}
+
+impl<'a, H: Hypergraph<'a>> Reduction<'a> for H {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::hypergraph::{HyperedgeImpl, HypergraphImpl, Node};
+    use crate::interfaces::edge::NodeSet;
+    use crate::interfaces::hypergraph::IdVector;
+
+    #[test]
+    fn reduces_to_maximal_faces() {
+        let mut hypergraph = HypergraphImpl::new();
+        for id in 0..5 {
+            hypergraph.add_node(Node::from_id(id));
+        }
+        for nodes in [vec![0, 1], vec![0, 1, 2], vec![1, 0], vec![3], vec![0, 1, 2, 3], vec![4], vec![1, 2]] {
+            hypergraph.add_hyperedge(HyperedgeImpl::from_nodes(nodes));
+        }
+
+        let report = hypergraph.containment_report();
+        assert_eq!(report.duplicates(), vec![vec![0, 2]]);
+        assert_eq!(report.parents[0], vec![1]);
+        assert_eq!(report.parents[3], vec![4]);
+        assert_eq!(report.maximal(), vec![4, 5]);
+        assert_eq!(report.chains(), vec![vec![0, 1, 4], vec![3, 4], vec![5], vec![6, 1, 4]]);
+
+        assert_eq!(hypergraph.deduplicated().hyperedges().count(), 6);
+        let reduced = hypergraph.reduced();
+        assert_eq!(reduced.hyperedges().map(|e| e.id()).collect::<Vec<_>>(), vec![vec![0, 1, 2, 3], vec![4]]);
+        assert_eq!(reduced.nodes().count(), 5);
+    }
+}
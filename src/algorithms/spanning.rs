@@ -0,0 +1,336 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::interfaces::graph::{Graph, IdPair, SingleId, UnDirected};
+
+/// Chosen edges and their total weight. For a disconnected graph the spanning methods return a spanning forest.
+#[derive(Clone, Debug)]
+pub struct SpanningForest<'a, E> {
+    pub edges: Vec<&'a E>,
+    pub weight: f64,
+}
+
+/// Disjoint sets over `0..n`, with union by size and path halving.
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        UnionFind { parent: (0..n).collect(), size: vec![1; n] }
+    }
+
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merge the sets of `x` and `y`; `false` if they were already one.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut x, mut y) = (self.find(x), self.find(y));
+        if x == y {
+            return false;
+        }
+        if self.size[x] < self.size[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        true
+    }
+}
+
+// An edge by positions, ordered by weight and then position so that ties always break the same way.
+#[derive(Clone, Copy, Debug)]
+struct Link {
+    u: usize,
+    v: usize,
+    weight: f64,
+    position: usize,
+}
+
+impl PartialEq for Link {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Link {}
+
+impl PartialOrd for Link {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Link {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.weight.total_cmp(&other.weight).then(self.position.cmp(&other.position))
+    }
+}
+
+// Node positions, the edges in graph order and the links between them, without self-loops or edges with an endpoint
+// that is not a node.
+type Prepared<'a, G> = (Vec<&'a <G as Graph<'a>>::Node>, Vec<&'a <G as Graph<'a>>::Edge>, Vec<Link>);
+
+fn links<'a, G: Graph<'a>>(graph: &'a G, weight: impl Fn(usize, &G::Edge) -> f64) -> Prepared<'a, G> {
+    let nodes = graph.nodes().collect::<Vec<_>>();
+    let index = nodes.iter().enumerate().map(|(i, node)| (node.id(), i)).collect::<HashMap<_, _>>();
+    let edges = graph.edges().collect::<Vec<_>>();
+    let links = edges.iter().enumerate().filter_map(|(position, edge)| {
+        let (src, dst) = edge.pair();
+        let (&u, &v) = (index.get(&src)?, index.get(&dst)?);
+        (u != v).then(|| Link { u, v, weight: weight(position, edge), position })
+    }).collect();
+    (nodes, edges, links)
+}
+
+fn kruskal(n: usize, mut links: Vec<Link>) -> Vec<Link> {
+    links.sort_unstable();
+    let mut sets = UnionFind::new(n);
+    links.into_iter().filter(|link| sets.union(link.u, link.v)).collect()
+}
+
+fn prim(n: usize, links: &[Link]) -> Vec<Link> {
+    let mut incident = vec![Vec::new(); n];
+    for link in links.iter() {
+        incident[link.u].push(*link);
+        incident[link.v].push(*link);
+    }
+    let mut reached = vec![false; n];
+    let mut chosen = Vec::new();
+    for root in 0..n {
+        if reached[root] {
+            continue;
+        }
+        reached[root] = true;
+        let mut heap = incident[root].iter().map(|&link| Reverse(link)).collect::<BinaryHeap<_>>();
+        while let Some(Reverse(link)) = heap.pop() {
+            let next = if reached[link.u] { link.v } else { link.u };
+            if reached[next] {
+                continue;
+            }
+            reached[next] = true;
+            chosen.push(link);
+            heap.extend(incident[next].iter().filter(|l| !reached[l.u] || !reached[l.v]).map(|&l| Reverse(l)));
+        }
+    }
+    chosen
+}
+
+fn boruvka(n: usize, links: &[Link]) -> Vec<Link> {
+    let mut sets = UnionFind::new(n);
+    let mut chosen = Vec::new();
+    loop {
+        // The lightest link leaving each component; the strict order on links keeps the choices acyclic.
+        let mut cheapest: Vec<Option<Link>> = vec![None; n];
+        for link in links.iter() {
+            let (a, b) = (sets.find(link.u), sets.find(link.v));
+            if a == b {
+                continue;
+            }
+            for c in [a, b] {
+                if cheapest[c].is_none_or(|best| *link < best) {
+                    cheapest[c] = Some(*link);
+                }
+            }
+        }
+        let before = chosen.len();
+        for link in cheapest.into_iter().flatten() {
+            if sets.union(link.u, link.v) {
+                chosen.push(link);
+            }
+        }
+        if chosen.len() == before {
+            return chosen;
+        }
+    }
+}
+
+fn forest<'a, E>(edges: &[&'a E], chosen: Vec<Link>) -> SpanningForest<'a, E> {
+    SpanningForest { weight: chosen.iter().map(|link| link.weight).sum(), edges: chosen.into_iter().map(|link| edges[link.position]).collect() }
+}
+
+#[derive(PartialEq)]
+struct Distance(f64, usize);
+
+impl Eq for Distance {}
+
+impl PartialOrd for Distance {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Distance {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+// Shortest distances from `s` with the link used to reach each node.
+fn dijkstra(incident: &[Vec<Link>], s: usize) -> (Vec<f64>, Vec<Option<Link>>) {
+    let n = incident.len();
+    let (mut dist, mut via) = (vec![f64::INFINITY; n], vec![None; n]);
+    dist[s] = 0.0;
+    let mut heap = BinaryHeap::from([Reverse(Distance(0.0, s))]);
+    while let Some(Reverse(Distance(d, u))) = heap.pop() {
+        if d > dist[u] {
+            continue;
+        }
+        for link in incident[u].iter() {
+            let v = if link.u == u { link.v } else { link.u };
+            let candidate = d + link.weight;
+            if candidate < dist[v] {
+                dist[v] = candidate;
+                via[v] = Some(*link);
+                heap.push(Reverse(Distance(candidate, v)));
+            }
+        }
+    }
+    (dist, via)
+}
+
+/// Minimum spanning forests of an undirected graph. `weight` gives each edge's weight from its position in
+/// `Graph::edges` and the edge itself; self-loops are never chosen. Ties break by position, so all three algorithms
+/// choose the same edges.
+pub trait SpanningTree<'a>: Graph<'a> + UnDirected + Sized {
+    fn kruskal(&'a self, weight: impl Fn(usize, &Self::Edge) -> f64) -> SpanningForest<'a, Self::Edge> {
+        let (nodes, edges, links) = links(self, weight);
+        forest(&edges, kruskal(nodes.len(), links))
+    }
+
+    fn prim(&'a self, weight: impl Fn(usize, &Self::Edge) -> f64) -> SpanningForest<'a, Self::Edge> {
+        let (nodes, edges, links) = links(self, weight);
+        forest(&edges, prim(nodes.len(), &links))
+    }
+
+    fn boruvka(&'a self, weight: impl Fn(usize, &Self::Edge) -> f64) -> SpanningForest<'a, Self::Edge> {
+        let (nodes, edges, links) = links(self, weight);
+        forest(&edges, boruvka(nodes.len(), &links))
+    }
+
+    /// A tree connecting `terminals` of weight at most twice the optimum (Kou, Markowsky and Berman): a minimum spanning
+    /// tree of the terminals' shortest-path distances, expanded into paths, re-spanned and stripped of non-terminal
+    /// leaves. Weights must be non-negative. `None` if a terminal is not in the graph or the terminals are not all connected.
+    fn steiner_tree(&'a self, terminals: &[&Self::Node], weight: impl Fn(usize, &Self::Edge) -> f64) -> Option<SpanningForest<'a, Self::Edge>> {
+        let (nodes, edges, links) = links(self, weight);
+        let n = nodes.len();
+        let index = nodes.iter().enumerate().map(|(i, node)| (node.id(), i)).collect::<HashMap<_, _>>();
+        let mut terminals = terminals.iter().map(|node| index.get(&node.id()).copied()).collect::<Option<Vec<_>>>()?;
+        terminals.sort_unstable();
+        terminals.dedup();
+        let mut incident = vec![Vec::new(); n];
+        for link in links.iter() {
+            incident[link.u].push(*link);
+            incident[link.v].push(*link);
+        }
+
+        // Minimum spanning tree of the complete distance graph on the terminals.
+        let paths = terminals.iter().map(|&t| dijkstra(&incident, t)).collect::<Vec<_>>();
+        let mut closure = Vec::new();
+        for (i, (dist, _)) in paths.iter().enumerate() {
+            for (j, &t) in terminals.iter().enumerate().skip(i + 1) {
+                let weight = dist[t];
+                if weight.is_infinite() {
+                    return None;
+                }
+                closure.push(Link { u: i, v: j, weight, position: closure.len() });
+            }
+        }
+        let mut on_path = vec![false; n];
+        terminals.iter().for_each(|&t| on_path[t] = true);
+        for link in kruskal(terminals.len(), closure) {
+            let via = &paths[link.u].1;
+            let mut v = terminals[link.v];
+            while let Some(step) = via[v] {
+                v = if step.u == v { step.v } else { step.u };
+                on_path[v] = true;
+            }
+        }
+
+        // Re-span the subgraph induced by the path nodes, then drop non-terminal leaves until none are left.
+        let induced = links.iter().filter(|link| on_path[link.u] && on_path[link.v]).copied().collect();
+        let mut tree = kruskal(n, induced);
+        let is_terminal = terminals.iter().copied().collect::<HashSet<_>>();
+        loop {
+            let mut degree = vec![0; n];
+            for link in tree.iter() {
+                degree[link.u] += 1;
+                degree[link.v] += 1;
+            }
+            let leaf = |u: usize| degree[u] == 1 && !is_terminal.contains(&u);
+            let before = tree.len();
+            tree.retain(|link| !leaf(link.u) && !leaf(link.v));
+            if tree.len() == before {
+                return Some(forest(&edges, tree));
+            }
+        }
+    }
+}
+
+impl<'a, G: Graph<'a> + UnDirected> SpanningTree<'a> for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::standard::{SingleLabel, StandardUnDirectedGraph};
+
+    #[test]
+    fn spanning_trees_agree() {
+        let mut graph = StandardUnDirectedGraph::new();
+        for id in 0..6 {
+            graph.add_node(id, String::new());
+        }
+        // A weighted 5-node graph plus an isolated node 5, so the result is a forest, and an edge to a missing node.
+        let weighted = [(0, 1, 4.0), (0, 2, 1.0), (1, 2, 2.0), (1, 3, 5.0), (2, 3, 8.0), (3, 4, 3.0), (2, 4, 9.0), (4, 4, 0.0), (5, 9, 0.0)];
+        for &(src, dst, _) in weighted.iter() {
+            graph.add_edge(src, dst, SingleLabel::default());
+        }
+        let weight = |i: usize, _: &_| weighted[i].2;
+        let expected = graph.kruskal(weight);
+        assert_eq!(expected.weight, 11.0);
+        assert_eq!(expected.edges.len(), 4);
+        for found in [graph.prim(weight), graph.boruvka(weight)] {
+            assert_eq!(found.weight, expected.weight);
+            let mut pairs = found.edges.iter().map(|edge| edge.pair()).collect::<Vec<_>>();
+            let mut expected_pairs = expected.edges.iter().map(|edge| edge.pair()).collect::<Vec<_>>();
+            pairs.sort();
+            expected_pairs.sort();
+            assert_eq!(pairs, expected_pairs);
+        }
+    }
+
+    #[test]
+    fn steiner_tree_uses_the_hub() {
+        // Three terminals around a hub: the star through 3 (weight 3) beats any path between terminals (weight 4).
+        let mut graph = StandardUnDirectedGraph::new();
+        for id in 0..5 {
+            graph.add_node(id, String::new());
+        }
+        let weighted = [(0, 3, 1.0), (1, 3, 1.0), (2, 3, 1.0), (0, 1, 2.0), (1, 2, 2.0), (3, 4, 0.5), (0, 9, 0.0)];
+        for &(src, dst, _) in weighted.iter() {
+            graph.add_edge(src, dst, SingleLabel::default());
+        }
+        let nodes = graph.nodes().collect::<Vec<_>>();
+        let tree = graph.steiner_tree(&[nodes[0], nodes[1], nodes[2]], |i, _| weighted[i].2).unwrap();
+        assert_eq!(tree.weight, 3.0);
+        let mut pairs = tree.edges.iter().map(|edge| edge.pair()).collect::<Vec<_>>();
+        pairs.sort_unstable();
+        assert_eq!(pairs, vec![(0, 3), (1, 3), (2, 3)]);
+
+        graph.add_node(5, String::new());
+        let nodes = graph.nodes().collect::<Vec<_>>();
+        assert!(graph.steiner_tree(&[nodes[0], nodes[5]], |i, _| weighted[i].2).is_none());
+
+        let mut other = StandardUnDirectedGraph::new();
+        other.add_node(9, String::new());
+        let stranger = other.nodes().next().unwrap();
+        assert!(graph.steiner_tree(&[nodes[0], stranger], |i, _| weighted[i].2).is_none());
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+
+use crate::interfaces::edge::Hyperedge;
+use crate::interfaces::graph::{Graph, IdPair, SingleId};
+use crate::interfaces::hypergraph::Hypergraph;
+
+// The simple undirected graph underneath: sorted neighbour positions, without self-loops, repeated edges or edges
+// with an endpoint that is not a node.
+fn simple_neighbours<'a, G: Graph<'a>>(graph: &'a G) -> (Vec<&'a G::Node>, Vec<Vec<usize>>) {
+    let nodes = graph.nodes().collect::<Vec<_>>();
+    let index = nodes.iter().enumerate().map(|(i, node)| (node.id(), i)).collect::<HashMap<_, _>>();
+    let mut neighbours = vec![Vec::new(); nodes.len()];
+    for edge in graph.edges() {
+        let (src, dst) = edge.pair();
+        let (Some(&u), Some(&v)) = (index.get(&src), index.get(&dst)) else {
+            continue;
+        };
+        if u != v {
+            neighbours[u].push(v);
+            neighbours[v].push(u);
+        }
+    }
+    for row in neighbours.iter_mut() {
+        row.sort_unstable();
+        row.dedup();
+    }
+    (nodes, neighbours)
+}
+
+fn triangles(neighbours: &[Vec<usize>]) -> Vec<usize> {
+    let n = neighbours.len();
+    // Each triangle is found once, from its lowest vertex in (degree, position) order.
+    let rank = |u: usize| (neighbours[u].len(), u);
+    let forward = (0..n).map(|u| neighbours[u].iter().copied().filter(|&v| rank(v) > rank(u)).collect::<Vec<_>>()).collect::<Vec<_>>();
+    let mut counts = vec![0; n];
+    for u in 0..n {
+        for &v in forward[u].iter() {
+            let (mut i, mut j) = (0, 0);
+            let (a, b) = (&forward[u], &forward[v]);
+            while i < a.len() && j < b.len() {
+                if a[i] < b[j] {
+                    i += 1;
+                } else if a[i] > b[j] {
+                    j += 1;
+                } else {
+                    counts[u] += 1;
+                    counts[v] += 1;
+                    counts[a[i]] += 1;
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+    }
+    counts
+}
+
+// Batagelj and Zaversnik's bucket peeling: core numbers and the order nodes were removed in.
+fn peel(neighbours: &[Vec<usize>]) -> (Vec<usize>, Vec<usize>) {
+    let n = neighbours.len();
+    let mut degree = neighbours.iter().map(|row| row.len()).collect::<Vec<_>>();
+    let max = degree.iter().copied().max().unwrap_or(0);
+    let mut start = vec![0; max + 2];
+    for &d in degree.iter() {
+        start[d + 1] += 1;
+    }
+    for d in 1..start.len() {
+        start[d] += start[d - 1];
+    }
+    let (mut order, mut position) = (vec![0; n], vec![0; n]);
+    let mut next = start.clone();
+    for u in 0..n {
+        position[u] = next[degree[u]];
+        order[position[u]] = u;
+        next[degree[u]] += 1;
+    }
+    for i in 0..n {
+        let u = order[i];
+        for &v in neighbours[u].iter() {
+            if degree[v] > degree[u] {
+                // Swap `v` to the front of its bucket, then shrink the bucket past it.
+                let (dv, pv) = (degree[v], position[v]);
+                let (pw, w) = (start[dv], order[start[dv]]);
+                if v != w {
+                    order.swap(pv, pw);
+                    position[v] = pw;
+                    position[w] = pv;
+                }
+                start[dv] += 1;
+                degree[v] -= 1;
+            }
+        }
+    }
+    (degree, order)
+}
+
+/// Triangles, clustering and cores of a graph, with edges read as undirected and self-loops and repeated edges ignored.
+pub trait Structure<'a>: Graph<'a> + Sized {
+    /// The number of triangles each node is part of.
+    fn triangles(&'a self) -> HashMap<&'a Self::Node, usize> {
+        let (nodes, neighbours) = simple_neighbours(self);
+        nodes.into_iter().zip(triangles(&neighbours)).collect()
+    }
+
+    fn triangle_count(&'a self) -> usize {
+        let (_, neighbours) = simple_neighbours(self);
+        triangles(&neighbours).iter().sum::<usize>() / 3
+    }
+
+    /// The fraction of pairs of neighbours that are adjacent; 0 for nodes with fewer than two neighbours.
+    fn clustering(&'a self) -> HashMap<&'a Self::Node, f64> {
+        let (nodes, neighbours) = simple_neighbours(self);
+        let coefficients = triangles(&neighbours).into_iter().zip(neighbours.iter()).map(|(t, row)| {
+            let d = row.len();
+            if d < 2 { 0.0 } else { 2.0 * t as f64 / (d * (d - 1)) as f64 }
+        });
+        nodes.into_iter().zip(coefficients).collect()
+    }
+
+    /// The mean local clustering coefficient over all nodes.
+    fn average_clustering(&'a self) -> f64 {
+        let clustering = self.clustering();
+        if clustering.is_empty() {
+            return 0.0;
+        }
+        clustering.values().sum::<f64>() / clustering.len() as f64
+    }
+
+    /// The largest `k` such that each node is in the `k`-core.
+    fn core_numbers(&'a self) -> HashMap<&'a Self::Node, usize> {
+        let (nodes, neighbours) = simple_neighbours(self);
+        nodes.into_iter().zip(peel(&neighbours).0).collect()
+    }
+
+    /// The nodes of the maximal subgraph in which every node has at least `k` neighbours, in graph order.
+    fn k_core(&'a self, k: usize) -> Vec<&'a Self::Node> {
+        let (nodes, neighbours) = simple_neighbours(self);
+        nodes.into_iter().zip(peel(&neighbours).0).filter(|&(_, core)| core >= k).map(|(node, _)| node).collect()
+    }
+
+    /// Nodes in the order a smallest-degree-first peeling removes them: each has at most `degeneracy` neighbours later
+    /// in the order.
+    fn degeneracy_ordering(&'a self) -> Vec<&'a Self::Node> {
+        let (nodes, neighbours) = simple_neighbours(self);
+        peel(&neighbours).1.into_iter().map(|u| nodes[u]).collect()
+    }
+
+    fn degeneracy(&'a self) -> usize {
+        let (_, neighbours) = simple_neighbours(self);
+        peel(&neighbours).0.into_iter().max().unwrap_or(0)
+    }
+}
+
+impl<'a, G: Graph<'a>> Structure<'a> for G {}
+
+/// Cores of a hypergraph. A hyperedge belongs to the sub-hypergraph induced by a node set when all of its members are
+/// in the set, so removing a node removes every hyperedge it is in. Empty hyperedges are ignored.
+pub trait HypergraphStructure<'a>: Hypergraph<'a> + Sized {
+    /// The largest `k` such that each node is in the hypergraph `k`-core.
+    fn hyper_core_numbers(&'a self) -> HashMap<&'a Self::Node, usize> {
+        let (nodes, _, core) = hyper_peel(self);
+        nodes.into_iter().zip(core).collect()
+    }
+
+    /// The maximal induced sub-hypergraph in which every node is in at least `k` hyperedges: its nodes in hypergraph
+    /// order and its hyperedges.
+    fn hyper_k_core(&'a self, k: usize) -> (Vec<&'a Self::Node>, Vec<&'a Self::Edge>) {
+        let (nodes, edges, core) = hyper_peel(self);
+        let kept = core.iter().map(|&c| c >= k).collect::<Vec<_>>();
+        let edges = edges.into_iter().filter(|(_, members)| members.iter().all(|&v| kept[v])).map(|(edge, _)| edge).collect();
+        let nodes = nodes.into_iter().zip(kept).filter(|&(_, keep)| keep).map(|(node, _)| node).collect();
+        (nodes, edges)
+    }
+}
+
+impl<'a, H: Hypergraph<'a>> HypergraphStructure<'a> for H {}
+
+type Incidence<'a, H> = (Vec<&'a <H as Hypergraph<'a>>::Node>, Vec<(&'a <H as Hypergraph<'a>>::Edge, Vec<usize>)>, Vec<usize>);
+
+// Repeatedly remove a node of least remaining degree, with its hyperedges; a node's core number is the largest
+// degree at which a node has been removed so far. Hyperedges with members outside the hypergraph are never counted.
+fn hyper_peel<'a, H: Hypergraph<'a>>(hypergraph: &'a H) -> Incidence<'a, H> {
+    let nodes = hypergraph.nodes().collect::<Vec<_>>();
+    let n = nodes.len();
+    let index = nodes.iter().enumerate().map(|(i, node)| (node.id(), i)).collect::<HashMap<_, _>>();
+    let edges = hypergraph.hyperedges().filter_map(|edge| {
+        let members = edge.sorted_ids().iter().map(|id| index.get(id).copied()).collect::<Option<Vec<_>>>()?;
+        (!members.is_empty()).then_some((edge, members))
+    }).collect::<Vec<_>>();
+    let mut incident = vec![Vec::new(); n];
+    for (e, (_, members)) in edges.iter().enumerate() {
+        for &v in members.iter() {
+            incident[v].push(e);
+        }
+    }
+
+    let mut degree = incident.iter().map(|row| row.len()).collect::<Vec<_>>();
+    let mut buckets = vec![Vec::new(); degree.iter().copied().max().unwrap_or(0) + 1];
+    for (v, &d) in degree.iter().enumerate() {
+        buckets[d].push(v);
+    }
+    let (mut removed, mut alive) = (vec![false; n], vec![true; edges.len()]);
+    let mut core = vec![0; n];
+    let (mut level, mut d) = (0, 0);
+    for _ in 0..n {
+        // Buckets hold stale entries; skip nodes already removed or whose degree has since dropped.
+        let v = loop {
+            match buckets[d].pop() {
+                Some(v) if !removed[v] && degree[v] == d => break v,
+                Some(_) => {}
+                None => d += 1,
+            }
+        };
+        level = level.max(d);
+        core[v] = level;
+        removed[v] = true;
+        for &e in incident[v].iter() {
+            if !alive[e] {
+                continue;
+            }
+            alive[e] = false;
+            for &u in edges[e].1.iter() {
+                if !removed[u] {
+                    degree[u] -= 1;
+                    buckets[degree[u]].push(u);
+                    d = d.min(degree[u]);
+                }
+            }
+        }
+    }
+    (nodes, edges, core)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::hypergraph::{HyperedgeImpl, HypergraphImpl, Node, NodeType, Desc};
+    use crate::impls::standard::{SingleLabel, StandardUnDirectedGraph};
+
+    fn by_id<N: SingleId, T>(values: HashMap<&N, T>) -> HashMap<usize, T> {
+        values.into_iter().map(|(node, value)| (node.id(), value)).collect()
+    }
+
+    #[test]
+    fn triangles_and_cores() {
+        // A 4-clique on 0..4 with a tail 3 - 4 - 5, a repeated edge and one to a missing node.
+        let mut graph = StandardUnDirectedGraph::new();
+        for id in 0..6 {
+            graph.add_node(id, String::new());
+        }
+        for (src, dst) in [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3), (3, 4), (4, 5), (1, 0), (5, 9)] {
+            graph.add_edge(src, dst, SingleLabel::default());
+        }
+
+        assert_eq!(graph.triangle_count(), 4);
+        let triangles = by_id(graph.triangles());
+        assert_eq!((triangles[&0], triangles[&3], triangles[&4]), (3, 3, 0));
+        let clustering = by_id(graph.clustering());
+        assert_eq!((clustering[&0], clustering[&3], clustering[&5]), (1.0, 0.5, 0.0));
+        assert!((graph.average_clustering() - 3.5 / 6.0).abs() < 1e-12);
+
+        let cores = by_id(graph.core_numbers());
+        assert_eq!((0..6).map(|id| cores[&id]).collect::<Vec<_>>(), vec![3, 3, 3, 3, 1, 1]);
+        assert_eq!(graph.k_core(3).iter().map(|node| node.id()).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(graph.degeneracy(), 3);
+        let order = graph.degeneracy_ordering().iter().map(|node| node.id()).collect::<Vec<_>>();
+        assert_eq!(order[..2], [5, 4]);
+    }
+
+    #[test]
+    fn hypergraph_core_drops_hyperedges_of_removed_nodes() {
+        let mut hypergraph = HypergraphImpl::new();
+        for id in 0..5 {
+            hypergraph.add_node(Node::new(id, NodeType::new(0), Desc::default()));
+        }
+        for pins in [vec![0, 1], vec![1, 2], vec![0, 2], vec![0, 1, 2], vec![2, 3], vec![3, 4]] {
+            hypergraph.add_hyperedge(HyperedgeImpl::new(pins, 1.0, String::new(), 0));
+        }
+        let (nodes, edges) = hypergraph.hyper_k_core(3);
+        assert_eq!(nodes.iter().map(|node| node.id()).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(edges.len(), 4);
+        let cores = by_id(hypergraph.hyper_core_numbers());
+        assert_eq!((0..5).map(|id| cores[&id]).collect::<Vec<_>>(), vec![3, 3, 3, 1, 1]);
+    }
+}
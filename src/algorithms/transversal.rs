@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use crate::interfaces::edge::{Hyperedge, NodeSet};
+use crate::interfaces::hypergraph::{Hypergraph, ToOwnedHypergraph};
+
+// Hyperedges over dense indices `0..ids.len()`, with the incidence list of every index.
+// Hitting only depends on membership, so every kind of hyperedge is read as the set of its members.
+struct Incidence {
+    ids: Vec<usize>,
+    edges: Vec<Vec<usize>>,
+    incident: Vec<Vec<usize>>,
+}
+
+impl Incidence {
+    fn new<'e, E: Hyperedge + 'e>(edges: impl Iterator<Item = &'e E>) -> Self {
+        let mut index = HashMap::new();
+        let mut ids = Vec::new();
+        let edges = edges.map(|edge| edge.sorted_ids().iter().map(|&id| {
+            *index.entry(id).or_insert_with(|| {
+                ids.push(id);
+                ids.len() - 1
+            })
+        }).collect::<Vec<_>>()).collect::<Vec<_>>();
+        let mut incident = vec![Vec::new(); ids.len()];
+        for (e, edge) in edges.iter().enumerate() {
+            for &v in edge {
+                incident[v].push(e);
+            }
+        }
+        Incidence { ids, edges, incident }
+    }
+
+    fn to_ids(&self, set: impl Iterator<Item = usize>) -> Vec<usize> {
+        let mut ids = set.map(|v| self.ids[v]).collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids
+    }
+}
+
+struct Frame {
+    branch: Vec<usize>,
+    pos: usize,
+    added: Option<usize>,
+}
+
+/// Lazily enumerates the minimal transversals of a hypergraph with the MMCS algorithm of Murakami and Uno.
+///
+/// Each transversal is yielded once, as ascending node ids, and the search keeps polynomial space. No output-polynomial
+/// algorithm is known for general hypergraphs; MMCS is among the fastest in practice. A hypergraph with an empty
+/// hyperedge has no transversal, and one without hyperedges has the empty transversal only.
+pub struct MinimalTransversals {
+    incidence: Incidence,
+    in_set: Vec<bool>,
+    in_cand: Vec<bool>,
+    set: Vec<usize>,
+    hits: Vec<usize>,
+    // For a member of the current set, how many hyperedges it alone hits.
+    critical: Vec<usize>,
+    stack: Vec<Frame>,
+    started: bool,
+}
+
+impl MinimalTransversals {
+    fn new(incidence: Incidence) -> Self {
+        let (n, m) = (incidence.ids.len(), incidence.edges.len());
+        MinimalTransversals {
+            incidence,
+            in_set: vec![false; n],
+            in_cand: vec![true; n],
+            set: Vec::new(),
+            hits: vec![0; m],
+            critical: vec![0; n],
+            stack: Vec::new(),
+            started: false,
+        }
+    }
+
+    fn add(&mut self, v: usize) {
+        self.in_set[v] = true;
+        self.set.push(v);
+        for &e in self.incidence.incident[v].iter() {
+            self.hits[e] += 1;
+            match self.hits[e] {
+                1 => self.critical[v] += 1,
+                2 => {
+                    let u = self.incidence.edges[e].iter().copied().find(|&u| u != v && self.in_set[u]).unwrap();
+                    self.critical[u] -= 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn remove(&mut self, v: usize) {
+        for &e in self.incidence.incident[v].iter() {
+            match self.hits[e] {
+                1 => self.critical[v] -= 1,
+                2 => {
+                    let u = self.incidence.edges[e].iter().copied().find(|&u| u != v && self.in_set[u]).unwrap();
+                    self.critical[u] += 1;
+                }
+                _ => {}
+            }
+            self.hits[e] -= 1;
+        }
+        self.in_set[v] = false;
+        self.set.pop();
+    }
+
+    // Push a frame branching on the uncovered hyperedge with the fewest candidates, or report that none is uncovered.
+    fn branch(&mut self) -> bool {
+        let uncovered = (0..self.incidence.edges.len()).filter(|&e| self.hits[e] == 0);
+        let candidates = |e: usize| self.incidence.edges[e].iter().copied().filter(|&v| self.in_cand[v]).collect::<Vec<_>>();
+        let Some(branch) = uncovered.map(candidates).min_by_key(Vec::len) else {
+            return false;
+        };
+        for &v in branch.iter() {
+            self.in_cand[v] = false;
+        }
+        self.stack.push(Frame { branch, pos: 0, added: None });
+        true
+    }
+}
+
+impl Iterator for MinimalTransversals {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            if !self.branch() {
+                return Some(Vec::new());
+            }
+        }
+        loop {
+            let frame = self.stack.last_mut()?;
+            let undo = frame.added.take();
+            let next = frame.branch.get(frame.pos).copied();
+            frame.pos += 1;
+            if let Some(v) = undo {
+                self.remove(v);
+                self.in_cand[v] = true;
+            }
+            let Some(v) = next else {
+                self.stack.pop();
+                continue;
+            };
+            self.add(v);
+            self.stack.last_mut().unwrap().added = Some(v);
+            if self.set.iter().any(|&u| self.critical[u] == 0) {
+                continue;
+            }
+            if !self.branch() {
+                return Some(self.incidence.to_ids(self.set.iter().copied()));
+            }
+        }
+    }
+}
+
+/// Minimal transversals (hitting sets) of a hypergraph. Transversals are returned as ascending node ids.
+pub trait Transversal<'a>: Hypergraph<'a> + Sized {
+    fn minimal_transversals(&'a self) -> MinimalTransversals {
+        MinimalTransversals::new(Incidence::new(self.hyperedges()))
+    }
+
+    /// Berge's algorithm: fold the hyperedges in one at a time, keeping the minimal transversals of those seen so far.
+    /// Simple, but intermediate results can be much larger than the answer.
+    fn berge_transversals(&'a self) -> Vec<Vec<usize>> {
+        let mut transversals = vec![Vec::new()];
+        for edge in self.hyperedges() {
+            let edge = edge.sorted_ids();
+            let mut next = Vec::new();
+            for t in transversals.iter() {
+                if t.iter().any(|id| edge.binary_search(id).is_ok()) {
+                    next.push(t.clone());
+                    continue;
+                }
+                for &id in edge.iter() {
+                    let mut grown = t.clone();
+                    let at = grown.binary_search(&id).unwrap_err();
+                    grown.insert(at, id);
+                    next.push(grown);
+                }
+            }
+            next.sort_by_key(Vec::len);
+            next.dedup();
+            transversals = Vec::new();
+            for t in next {
+                if !transversals.iter().any(|s: &Vec<usize>| s.iter().all(|id| t.binary_search(id).is_ok())) {
+                    transversals.push(t);
+                }
+            }
+        }
+        transversals.sort();
+        transversals
+    }
+
+    /// The transversal hypergraph: the same nodes, with one hyperedge per minimal transversal.
+    fn transversal_hypergraph(&'a self) -> Self::Owned
+    where Self: ToOwnedHypergraph<'a>, <Self::Owned as Hypergraph<'a>>::Edge: NodeSet {
+        let mut transversal = Self::Owned::new();
+        for node in self.nodes() {
+            transversal.add_node(Self::to_owned_node(node));
+        }
+        for t in self.minimal_transversals() {
+            transversal.add_hyperedge(NodeSet::from_nodes(t));
+        }
+        transversal
+    }
+
+    /// A small hitting set by the greedy rule: repeatedly take the node hitting the most hyperedges not yet hit, then drop
+    /// nodes that became redundant. Within a factor `ln(m) + 1` of the minimum. `None` when a hyperedge is empty.
+    fn greedy_hitting_set(&'a self) -> Option<Vec<usize>> {
+        let incidence = Incidence::new(self.hyperedges());
+        if incidence.edges.iter().any(Vec::is_empty) {
+            return None;
+        }
+        let mut hit = vec![false; incidence.edges.len()];
+        let mut gain = incidence.incident.iter().map(Vec::len).collect::<Vec<_>>();
+        let mut chosen = Vec::new();
+        while let Some(v) = (0..gain.len()).filter(|&v| gain[v] > 0).max_by_key(|&v| (gain[v], std::cmp::Reverse(incidence.ids[v]))) {
+            chosen.push(v);
+            for &e in incidence.incident[v].iter() {
+                if !hit[e] {
+                    hit[e] = true;
+                    for &u in incidence.edges[e].iter() {
+                        gain[u] -= 1;
+                    }
+                }
+            }
+        }
+
+        let mut hits = vec![0; incidence.edges.len()];
+        for &v in chosen.iter() {
+            for &e in incidence.incident[v].iter() {
+                hits[e] += 1;
+            }
+        }
+        let mut kept = vec![true; chosen.len()];
+        for i in (0..chosen.len()).rev() {
+            let v = chosen[i];
+            if incidence.incident[v].iter().all(|&e| hits[e] > 1) {
+                kept[i] = false;
+                for &e in incidence.incident[v].iter() {
+                    hits[e] -= 1;
+                }
+            }
+        }
+        Some(incidence.to_ids(chosen.into_iter().zip(kept).filter(|(_, k)| *k).map(|(v, _)| v)))
+    }
+}
+
+impl<'a, H: Hypergraph<'a>> Transversal<'a> for H {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::hypergraph::{HyperedgeImpl, HypergraphImpl};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn mmcs_agrees_with_berge() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..20 {
+            let mut hypergraph = HypergraphImpl::new();
+            for _ in 0..rng.random_range(0..7) {
+                let size = rng.random_range(1..4);
+                hypergraph.add_hyperedge(HyperedgeImpl::from_nodes((0..size).map(|_| rng.random_range(0..8)).collect()));
+            }
+            let mut mmcs = hypergraph.minimal_transversals().collect::<Vec<_>>();
+            mmcs.sort();
+            assert_eq!(mmcs, hypergraph.berge_transversals());
+
+            let greedy = hypergraph.greedy_hitting_set().unwrap();
+            let smallest = mmcs.iter().map(Vec::len).min().unwrap();
+            assert!(greedy.len() >= smallest);
+            assert!(hypergraph.hyperedges().all(|e| e.sorted_ids().iter().any(|id| greedy.contains(id))));
+        }
+
+        let mut hypergraph = HypergraphImpl::new();
+        hypergraph.add_hyperedge(HyperedgeImpl::from_nodes(vec![]));
+        assert_eq!(hypergraph.minimal_transversals().count(), 0);
+        assert_eq!(hypergraph.greedy_hitting_set(), None);
+    }
+}
@@ -0,0 +1,213 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use crate::interfaces::edge::{Hyperedge, NodeSet};
+use crate::interfaces::hypergraph::IdVector;
+
+/// A set of node ids stored as a bitset, one bit per id. Suited to dense ids: memory grows with the largest id.
+///
+/// Trailing zero words are trimmed, so equal sets compare and hash equally.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub struct NodeBitSet {
+    words: Vec<u64>,
+}
+
+impl NodeBitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_ids(ids: impl IntoIterator<Item = usize>) -> Self {
+        let mut set = Self::new();
+        for id in ids {
+            set.insert(id);
+        }
+        set
+    }
+
+    pub fn insert(&mut self, id: usize) {
+        let (word, bit) = (id / 64, id % 64);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << bit;
+    }
+
+    pub fn contains(&self, id: usize) -> bool {
+        self.words.get(id / 64).is_some_and(|word| word & (1 << (id % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// The ids in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                (rest != 0).then(|| {
+                    let bit = rest.trailing_zeros() as usize;
+                    rest &= rest - 1;
+                    i * 64 + bit
+                })
+            })
+        })
+    }
+
+    fn trimmed(mut words: Vec<u64>) -> Self {
+        while words.last() == Some(&0) {
+            words.pop();
+        }
+        NodeBitSet { words }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let (long, short) = if self.words.len() >= other.words.len() { (self, other) } else { (other, self) };
+        let mut words = long.words.clone();
+        for (word, o) in words.iter_mut().zip(short.words.iter()) {
+            *word |= o;
+        }
+        NodeBitSet { words }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::trimmed(self.words.iter().zip(other.words.iter()).map(|(a, b)| a & b).collect())
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let words = self.words.iter().enumerate().map(|(i, a)| a & !other.words.get(i).unwrap_or(&0)).collect();
+        Self::trimmed(words)
+    }
+
+    pub fn intersection_size(&self, other: &Self) -> usize {
+        self.words.iter().zip(other.words.iter()).map(|(a, b)| (a & b).count_ones() as usize).sum()
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words.iter().enumerate().all(|(i, a)| a & !other.words.get(i).unwrap_or(&0) == 0)
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.words.iter().zip(other.words.iter()).all(|(a, b)| a & b == 0)
+    }
+}
+
+/// A set hyperedge backed by a `NodeBitSet`, whose set algebra runs word by word.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub struct BitSetHyperedge {
+    members: NodeBitSet,
+}
+
+impl BitSetHyperedge {
+    pub fn members(&self) -> &NodeBitSet {
+        &self.members
+    }
+}
+
+impl From<NodeBitSet> for BitSetHyperedge {
+    fn from(members: NodeBitSet) -> Self {
+        BitSetHyperedge { members }
+    }
+}
+
+impl IdVector for BitSetHyperedge {
+    fn id(&self) -> Vec<usize> {
+        self.members.iter().collect()
+    }
+}
+
+impl NodeSet for BitSetHyperedge {
+    fn from_nodes(nodes: Vec<usize>) -> Self {
+        BitSetHyperedge { members: NodeBitSet::from_ids(nodes) }
+    }
+}
+
+impl Display for BitSetHyperedge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.id())
+    }
+}
+
+impl Hyperedge for BitSetHyperedge {
+    fn id_set(&self) -> HashSet<usize> {
+        self.members.iter().collect()
+    }
+
+    fn sorted_ids(&self) -> Cow<'_, [usize]> {
+        Cow::Owned(self.id())
+    }
+
+    fn sorted_members(&self) -> Cow<'_, [usize]> {
+        Cow::Owned(self.id())
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        self.members.is_subset(&other.members)
+    }
+
+    fn is_equal(&self, other: &Self) -> bool {
+        self.members == other.members
+    }
+
+    fn is_disjoint(&self, other: &Self) -> bool {
+        self.members.is_disjoint(&other.members)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    fn is_singleton(&self) -> bool {
+        self.members.len() == 1
+    }
+
+    fn union(&self, other: &Self) -> Vec<usize> {
+        self.members.union(&other.members).iter().collect()
+    }
+
+    fn intersection(&self, other: &Self) -> Vec<usize> {
+        self.members.intersection(&other.members).iter().collect()
+    }
+
+    fn difference(&self, other: &Self) -> Vec<usize> {
+        self.members.difference(&other.members).iter().collect()
+    }
+
+    fn intersection_size(&self, other: &Self) -> usize {
+        self.members.intersection_size(&other.members)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::hypergraph::{HyperedgeImpl, MultisetHyperedge};
+
+    #[test]
+    fn bitset_and_merge_algebra_agree() {
+        let (x, y) = (vec![3, 1, 130, 64, 3], vec![64, 2, 3, 200]);
+        let (bx, by) = (BitSetHyperedge::from_nodes(x.clone()), BitSetHyperedge::from_nodes(y.clone()));
+        let (sx, sy) = (HyperedgeImpl::from_nodes(x.clone()), HyperedgeImpl::from_nodes(y.clone()));
+
+        assert_eq!(bx.union(&by), vec![1, 2, 3, 64, 130, 200]);
+        assert_eq!(bx.union(&by), sx.union(&sy));
+        assert_eq!(bx.intersection(&by), sx.intersection(&sy));
+        assert_eq!(bx.difference(&by), vec![1, 130]);
+        assert_eq!(bx.difference(&by), sx.difference(&sy));
+        assert_eq!(bx.intersection_size(&by), 2);
+        assert!(bx.difference(&bx).is_empty() && BitSetHyperedge::from_nodes(vec![]).is_empty());
+        assert!(BitSetHyperedge::from_nodes(vec![64, 3]).is_subset(&by));
+        assert!(!by.is_subset(&bx) && !bx.is_disjoint(&by));
+
+        let (mx, my) = (MultisetHyperedge::from_nodes(x), MultisetHyperedge::from_nodes(y));
+        assert_eq!(mx.union(&my), vec![1, 2, 3, 3, 64, 130, 200]);
+        assert_eq!(mx.intersection(&my), vec![3, 64]);
+        assert_eq!(mx.difference(&my), vec![1, 3, 130]);
+    }
+}
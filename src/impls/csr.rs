@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::Arc;
+
+use crate::interfaces::graph::{Adjacency, AdjacencyInv, AdjacencyList, Directed, Graph, IdPair, SingleId};
+use crate::interfaces::labeled::{EdgeLabeled, Label, LabelIndex, LabelStatistics, Labeled, LabeledAdjacency, LabeledAdjacencyList};
+use crate::interfaces::vertex::Vertex;
+
+/// An interned label of a `CsrGraph`. Equal labels of one graph share their text; labels compare by text, so nodes and
+/// edges of different frozen graphs (a pattern and a data graph) compare correctly.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub struct CsrLabel(Arc<str>);
+
+impl Display for CsrLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Label for CsrLabel {
+    fn label(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A node of a `CsrGraph`. `index` is its position in the node array.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub struct CsrNode {
+    id: usize,
+    index: usize,
+    label: CsrLabel,
+}
+
+impl CsrNode {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl SingleId for CsrNode {
+    fn id(&self) -> usize {
+        self.id
+    }
+}
+
+impl Display for CsrNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[id: {}, label: {}]", self.id, self.label)
+    }
+}
+
+impl Vertex for CsrNode {}
+
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub struct CsrEdge {
+    src: usize,
+    dst: usize,
+    label: CsrLabel,
+}
+
+impl IdPair for CsrEdge {
+    fn pair(&self) -> (usize, usize) {
+        (self.src, self.dst)
+    }
+}
+
+/// A frozen, read-only directed labeled graph in compressed sparse row form.
+///
+/// Edges are stored grouped by source node; `out_offsets[i]..out_offsets[i + 1]` is the range of the `i`-th node's
+/// out-edges and `targets` holds their destination positions. The reverse direction is kept the same way.
+/// Node and edge labels are interned in a single table. `add_node` and `add_edge` panic.
+pub struct CsrGraph {
+    nodes: Vec<CsrNode>,
+    edges: Vec<CsrEdge>,
+    out_offsets: Vec<usize>,
+    targets: Vec<usize>,
+    in_offsets: Vec<usize>,
+    sources: Vec<usize>,
+    statistics: LabelIndex,
+}
+
+impl CsrGraph {
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn node_at(&self, index: usize) -> &CsrNode {
+        &self.nodes[index]
+    }
+
+    pub fn successors(&self, node: &CsrNode) -> impl Iterator<Item = &CsrNode> {
+        self.targets[self.out_offsets[node.index]..self.out_offsets[node.index + 1]].iter().map(|&t| &self.nodes[t])
+    }
+
+    pub fn predecessors(&self, node: &CsrNode) -> impl Iterator<Item = &CsrNode> {
+        self.sources[self.in_offsets[node.index]..self.in_offsets[node.index + 1]].iter().map(|&s| &self.nodes[s])
+    }
+
+    /// The out-edges of `node` paired with their destinations.
+    pub fn out_edges(&self, node: &CsrNode) -> impl Iterator<Item = (&CsrNode, &CsrEdge)> {
+        (self.out_offsets[node.index]..self.out_offsets[node.index + 1]).map(|e| (&self.nodes[self.targets[e]], &self.edges[e]))
+    }
+
+    pub fn out_degree(&self, node: &CsrNode) -> usize {
+        self.out_offsets[node.index + 1] - self.out_offsets[node.index]
+    }
+
+    pub fn in_degree(&self, node: &CsrNode) -> usize {
+        self.in_offsets[node.index + 1] - self.in_offsets[node.index]
+    }
+}
+
+// Prefix sums of per-node counts.
+fn offsets(counts: impl Iterator<Item = usize>, n: usize) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(n + 1);
+    offsets.push(0);
+    for count in counts {
+        offsets.push(offsets.last().unwrap() + count);
+    }
+    offsets
+}
+
+/// An edge endpoint that is not a node of the graph being frozen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownNode(pub usize);
+
+impl Display for UnknownNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "edge refers to unknown node {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownNode {}
+
+/// Freeze a labeled graph into a `CsrGraph`.
+pub trait Freeze<'a>: Graph<'a> {
+    fn freeze(&'a self) -> Result<CsrGraph, UnknownNode>;
+}
+
+impl<'a, G> Freeze<'a> for G
+where G: Graph<'a>, G::Node: Label, G::Edge: Label {
+    fn freeze(&'a self) -> Result<CsrGraph, UnknownNode> {
+        let mut interned: HashMap<&str, CsrLabel> = HashMap::new();
+        let mut intern = |label: &'a str| interned.entry(label).or_insert_with(|| CsrLabel(Arc::from(label))).clone();
+
+        let mut statistics = LabelIndex::new();
+        let mut index = HashMap::new();
+        let mut nodes = Vec::new();
+        for (i, node) in self.nodes().enumerate() {
+            index.insert(node.id(), i);
+            statistics.add_node(node.id(), node.label());
+            nodes.push(CsrNode { id: node.id(), index: i, label: intern(node.label()) });
+        }
+        let n = nodes.len();
+
+        let mut edges = self.edges().map(|edge| {
+            let (src, dst) = edge.pair();
+            let position = |id| index.get(&id).copied().ok_or(UnknownNode(id));
+            Ok((position(src)?, position(dst)?, CsrEdge { src, dst, label: intern(edge.label()) }))
+        }).collect::<Result<Vec<_>, _>>()?;
+        edges.sort_by_key(|edge| edge.0);
+
+        let mut out_counts = vec![0; n];
+        let mut in_counts = vec![0; n];
+        for (src, dst, _) in edges.iter() {
+            out_counts[*src] += 1;
+            in_counts[*dst] += 1;
+        }
+        let out_offsets = offsets(out_counts.into_iter(), n);
+        let in_offsets = offsets(in_counts.into_iter(), n);
+
+        let mut next = in_offsets.clone();
+        let mut sources = vec![0; edges.len()];
+        for (src, dst, _) in edges.iter() {
+            sources[next[*dst]] = *src;
+            next[*dst] += 1;
+        }
+        // The index follows the stored edge order, so positions agree with `edges()`.
+        for (_, _, edge) in edges.iter() {
+            statistics.add_edge(edge.src, edge.label.label(), edge.dst);
+        }
+        let targets = edges.iter().map(|edge| edge.1).collect();
+        let edges = edges.into_iter().map(|edge| edge.2).collect();
+
+        Ok(CsrGraph { nodes, edges, out_offsets, targets, in_offsets, sources, statistics })
+    }
+}
+
+impl<'a> Graph<'a> for CsrGraph {
+    type Node = CsrNode;
+    type Edge = CsrEdge;
+
+    fn new() -> Self {
+        CsrGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            out_offsets: vec![0],
+            targets: Vec::new(),
+            in_offsets: vec![0],
+            sources: Vec::new(),
+            statistics: LabelIndex::new(),
+        }
+    }
+
+    fn nodes(&'a self) -> impl Iterator<Item = &'a Self::Node> {
+        self.nodes.iter()
+    }
+
+    fn edges(&'a self) -> impl Iterator<Item = &'a Self::Edge> {
+        self.edges.iter()
+    }
+
+    fn get_edges_pair(&'a self) -> impl Iterator<Item = (&'a Self::Node, &'a Self::Node)> {
+        self.nodes.iter().flat_map(move |node| self.successors(node).map(move |dst| (node, dst)))
+    }
+
+    fn get_edges_pair_with_edge(&'a self) -> impl Iterator<Item = (&'a Self::Node, &'a Self::Edge, &'a Self::Node)> {
+        self.nodes.iter().flat_map(move |node| self.out_edges(node).map(move |(dst, edge)| (node, edge, dst)))
+    }
+
+    fn add_node(&mut self, _: Self::Node) {
+        panic!("a CsrGraph is frozen")
+    }
+
+    fn add_edge(&mut self, _: Self::Edge) {
+        panic!("a CsrGraph is frozen")
+    }
+}
+
+impl<'a> Labeled<'a> for CsrGraph {
+    fn label_same(&self, node: &Self::Node, label: &Self::Node) -> bool {
+        node.label == label.label
+    }
+
+    fn get_label(&'a self, node: &'a Self::Node) -> &'a impl Label {
+        &node.label
+    }
+
+    fn get_edges_pair_label(&'a self) -> impl Iterator<Item = (&'a Self::Node, &'a Self::Node, &'a impl Label)> {
+        self.nodes.iter().flat_map(move |node| self.out_edges(node).map(move |(dst, edge)| (node, dst, &edge.label)))
+    }
+
+    fn edge_label_same(&self, edge1: &Self::Edge, edge2: &Self::Edge) -> bool {
+        edge1.label == edge2.label
+    }
+
+    fn edge_node_label_same(&self, src1: &Self::Node, edge1: &Self::Edge, dst1: &Self::Node, src2: &Self::Node, edge2: &Self::Edge, dst2: &Self::Node) -> bool {
+        self.label_same(src1, src2) && self.edge_label_same(edge1, edge2) && self.label_same(dst1, dst2)
+    }
+}
+
+impl<'a> EdgeLabeled<'a> for CsrGraph {
+    fn get_edge_label(&'a self, edge: &'a Self::Edge) -> &'a impl Label {
+        &edge.label
+    }
+}
+
+impl<'a> LabelStatistics<'a> for CsrGraph {
+    fn label_index(&self) -> &LabelIndex {
+        &self.statistics
+    }
+
+    fn node_at(&'a self, position: usize) -> &'a Self::Node {
+        &self.nodes[position]
+    }
+
+    fn edge_at(&'a self, position: usize) -> &'a Self::Edge {
+        &self.edges[position]
+    }
+}
+
+impl Directed for CsrGraph {}
+
+// Lookups go straight to the CSR arrays, so the adjacency tables are left empty and cost nothing to build.
+impl<'a> Adjacency<'a> for CsrGraph {
+    fn get_adj(&'a self) -> AdjacencyList<'a, Self> {
+        AdjacencyList::default()
+    }
+
+    fn get_post(&'a self, _: &AdjacencyList<'a, Self>, node: &Self::Node) -> impl Iterator<Item = &'a Self::Node> {
+        self.successors(&self.nodes[node.index])
+    }
+}
+
+impl<'a> AdjacencyInv<'a> for CsrGraph {
+    fn get_adj_inv(&'a self) -> AdjacencyList<'a, Self> {
+        AdjacencyList::default()
+    }
+
+    fn get_pre(&'a self, _: &AdjacencyList<'a, Self>, node: &Self::Node) -> impl Iterator<Item = &'a Self::Node> {
+        self.predecessors(&self.nodes[node.index])
+    }
+}
+
+impl<'a> LabeledAdjacency<'a> for CsrGraph {
+    fn get_labeled_adj(&'a self) -> LabeledAdjacencyList<'a, Self> {
+        LabeledAdjacencyList::default()
+    }
+
+    fn get_labeled_post(&'a self, _: &LabeledAdjacencyList<'a, Self>, node: &Self::Node) -> impl Iterator<Item = (&'a Self::Node, &'a Self::Edge)> {
+        self.out_edges(&self.nodes[node.index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::standard::{SimpleLabeledGraph, StandardLabeledGraph};
+
+    #[test]
+    fn freeze_keeps_structure() {
+        let mut graph = StandardLabeledGraph::new();
+        graph.add_node(10, "a".to_string());
+        graph.add_node(20, "b".to_string());
+        graph.add_node(30, "a".to_string());
+        graph.add_edge(20, 10);
+        graph.add_edge(10, 30);
+        graph.add_edge(10, 20);
+
+        let csr = graph.freeze().unwrap();
+        let a = csr.node_at(0);
+        assert_eq!(csr.successors(a).map(|n| n.id()).collect::<Vec<_>>(), vec![30, 20]);
+        assert_eq!(csr.predecessors(a).map(|n| n.id()).collect::<Vec<_>>(), vec![20]);
+        assert!(csr.label_same(a, csr.node_at(2)));
+        assert!(!csr.label_same(a, csr.node_at(1)));
+        assert_eq!(csr.get_label(csr.node_at(1)).label(), "b");
+
+        // The tables are empty; lookups read the offsets directly.
+        let (adj, adj_inv, labeled) = (csr.get_adj(), csr.get_adj_inv(), csr.get_labeled_adj());
+        assert_eq!(csr.get_post(&adj, a).map(|n| n.id()).collect::<Vec<_>>(), vec![30, 20]);
+        assert_eq!(csr.get_pre(&adj_inv, a).map(|n| n.id()).collect::<Vec<_>>(), vec![20]);
+        assert_eq!(csr.get_labeled_post(&labeled, a).count(), 2);
+
+        // The label index follows the CSR edge order.
+        assert_eq!(csr.label_index().pair_count("a", "b"), 1);
+        assert_eq!(csr.nodes_labeled("a").map(|n| n.id()).collect::<Vec<_>>(), vec![10, 30]);
+        assert_eq!(csr.edges_labeled("").map(|e| e.pair()).collect::<Vec<_>>(), vec![(10, 30), (10, 20), (20, 10)]);
+    }
+
+    #[test]
+    fn patterns_from_other_graphs_compare_by_label() {
+        // The pattern interns `b` first, so its label numbering differs from the data graph's.
+        let mut data = SimpleLabeledGraph::<String, String>::new();
+        data.add_node(1, "a".to_string());
+        data.add_node(2, "b".to_string());
+        data.add_labeled_edge(1, 2, "x".to_string());
+        let mut pattern = SimpleLabeledGraph::<String, String>::new();
+        pattern.add_node(1, "b".to_string());
+        pattern.add_node(2, "a".to_string());
+        pattern.add_labeled_edge(1, 2, "y".to_string());
+        pattern.add_labeled_edge(2, 1, "x".to_string());
+
+        let (data, pattern) = (data.freeze().unwrap(), pattern.freeze().unwrap());
+        assert!(data.label_same(data.node_at(0), pattern.node_at(1)));
+        assert!(!data.label_same(data.node_at(0), pattern.node_at(0)));
+        let data_edge = data.edges().next().unwrap();
+        let pattern_edges = pattern.edges().collect::<Vec<_>>();
+        assert!(!data.edge_label_same(data_edge, pattern_edges[0]));
+        assert!(data.edge_label_same(data_edge, pattern_edges[1]));
+    }
+
+    #[test]
+    fn freeze_rejects_unknown_endpoints() {
+        let mut graph = StandardLabeledGraph::new();
+        graph.add_node(1, "a".to_string());
+        graph.add_edge(1, 2);
+        assert_eq!(graph.freeze().err(), Some(UnknownNode(2)));
+    }
+}
@@ -1,108 +1,231 @@
-use std::fmt::Display;
+use std::borrow::Cow;
+use std::fmt::{Debug, Display};
+use std::iter::Sum;
+use std::marker::PhantomData;
 use std::sync::RwLock;
 
-use crate::interfaces::edge::{DirectedHyperedge, Hyperedge, NodeSet};
+use crate::interfaces::edge::{DirectedHyperedge, Hyperedge, HyperedgeKind, NodeSet, NodeSetPair, Weighted};
 use crate::interfaces::graph::SingleId;
-use crate::interfaces::hypergraph::{Hypergraph, IdVector};
+use crate::interfaces::hypergraph::{Hypergraph, IdVector, ToOwnedHypergraph};
+use crate::interfaces::labeled::Label;
+use crate::interfaces::similarity::Embedding;
 use crate::interfaces::typed::Type;
 use crate::interfaces::vertex::Vertex;
 
 use std::{collections::{HashMap, HashSet}, hash::Hash, ops::{Add, BitXor, Div, Mul, Sub}};
 use rand::{prelude::*, rng};
 use rand::distr::StandardUniform;
-use serde::{Serialize, Deserialize};
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use serde::de::{DeserializeOwned, Error};
 use lazy_static::lazy_static;
 
 lazy_static!{
     static ref clusters: RwLock<HashMap<usize, Desc>> = RwLock::new(HashMap::new());
 }
-#[derive(Clone, Serialize, Deserialize)]
-pub struct Desc([f64; 16]);
 
-impl Hash for Desc {
+/// Element types of a `Descriptor`.
+pub trait Scalar: Copy + Default + PartialEq + Debug + Into<f64> + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Sum + Serialize + DeserializeOwned + 'static {
+    fn sqrt(self) -> Self;
+    fn to_f64(self) -> f64;
+    fn from_f64(value: f64) -> Self;
+    /// The bit pattern of the value, used for hashing.
+    fn hash_bits(self) -> u64;
+}
+
+impl Scalar for f32 {
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    fn hash_bits(self) -> u64 {
+        self.to_bits() as u64
+    }
+}
+
+impl Scalar for f64 {
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn hash_bits(self) -> u64 {
+        self.to_bits()
+    }
+}
+
+// `Descriptor`, `NodeType` and `FeatureNode` are `repr(C)` so that binary snapshots can map node arrays in place.
+/// A feature vector of `N` elements of type `T`.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Descriptor<T: Scalar, const N: usize>([T; N]);
+
+/// The 16-dimensional `f64` descriptor used by `Node`.
+pub type Desc = Descriptor<f64, 16>;
+
+impl<T: Scalar, const N: usize> Hash for Descriptor<T, N> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         for value in &self.0 {
-            let bits = value.to_bits();
-            bits.hash(state);
+            value.hash_bits().hash(state);
         }
     }
 }
 
-impl PartialEq for Desc {
+impl<T: Scalar, const N: usize> PartialEq for Descriptor<T, N> {
     fn eq(&self, other: &Self) -> bool {
         self.0.iter().zip(other.0.iter()).all(|(x, y)| x == y)
     }
 }
 
-impl Eq for Desc {}
+impl<T: Scalar, const N: usize> Eq for Descriptor<T, N> {}
+
+// Serialized as a plain sequence, since serde only derives arrays of up to 32 elements.
+impl<T: Scalar, const N: usize> Serialize for Descriptor<T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+impl<'de, T: Scalar, const N: usize> Deserialize<'de> for Descriptor<T, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<T>::deserialize(deserializer)?;
+        let len = values.len();
+        Descriptor::try_from(values).map_err(|_| D::Error::invalid_length(len, &format!("{} values", N).as_str()))
+    }
+}
+
+impl<T: Scalar, const N: usize> Descriptor<T, N> {
+    pub fn new(values: [T; N]) -> Self {
+        Descriptor(values)
+    }
+
+    pub fn values(&self) -> &[T; N] {
+        &self.0
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    pub fn dim(&self) -> usize {
+        N
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.0.iter().zip(other.0.iter()).map(|(&x, &y)| x * y).sum()
+    }
+
+    pub fn norm(&self) -> T {
+        self.dot(self).sqrt()
+    }
+
+    pub fn l2_distance(&self, other: &Self) -> T {
+        self.0.iter().zip(other.0.iter()).map(|(&x, &y)| (x - y) * (x - y)).sum::<T>().sqrt()
+    }
+
+    /// The cosine of the angle between two descriptors. It is NaN when either of them is zero.
+    pub fn cosine(&self, other: &Self) -> T {
+        self.dot(other) / (self.norm() * other.norm())
+    }
+
+    /// This descriptor scaled to unit length. A zero descriptor is returned unchanged.
+    pub fn normalize(&self) -> Self {
+        let norm = self.norm();
+        if norm == T::default() {
+            return self.clone();
+        }
+        Descriptor(self.0.map(|x| x / norm))
+    }
+
+    fn zip_with(self, other: Self, f: impl Fn(T, T) -> T) -> Self {
+        let mut result = self.0;
+        for (x, y) in result.iter_mut().zip(other.0) {
+            *x = f(*x, y);
+        }
+        Descriptor(result)
+    }
+}
+
+impl<T: Scalar, const N: usize> Default for Descriptor<T, N> {
+    fn default() -> Self {
+        Descriptor([T::default(); N])
+    }
+}
+
+impl<T: Scalar, const N: usize> TryFrom<&[T]> for Descriptor<T, N> {
+    type Error = std::array::TryFromSliceError;
+    fn try_from(values: &[T]) -> Result<Self, Self::Error> {
+        Ok(Descriptor(values.try_into()?))
+    }
+}
 
-impl Add for Desc {
+impl<T: Scalar, const N: usize> TryFrom<Vec<T>> for Descriptor<T, N> {
+    type Error = Vec<T>;
+    fn try_from(values: Vec<T>) -> Result<Self, Self::Error> {
+        Ok(Descriptor(values.try_into()?))
+    }
+}
+
+impl<T: Scalar, const N: usize> Add for Descriptor<T, N> {
     type Output = Self;
     fn add(self, other: Self) -> Self {
-        let mut result = [0.0; 16];
-        for i in 0..16 {
-            result[i] = self.0[i] + other.0[i];
-        }
-        Desc(result)
+        self.zip_with(other, |x, y| x + y)
     }
 }
 
-impl Sub for Desc {
+impl<T: Scalar, const N: usize> Sub for Descriptor<T, N> {
     type Output = Self;
     fn sub(self, other: Self) -> Self {
-        let mut result = [0.0; 16];
-        for i in 0..16 {
-            result[i] = self.0[i] - other.0[i];
-        }
-        Desc(result)
+        self.zip_with(other, |x, y| x - y)
     }
 }
 
-impl Mul for Desc {
-    type Output = f64;
-    fn mul(self, other: Self) -> f64 {
-        let mut result = 0.0;
-        for i in 0..16 {
-            result += self.0[i] * other.0[i];
-        }
-        result
+// dot product
+impl<T: Scalar, const N: usize> Mul for Descriptor<T, N> {
+    type Output = T;
+    fn mul(self, other: Self) -> T {
+        self.dot(&other)
     }
 }
 
-impl Mul<f64> for Desc {
-    type Output = Desc;
-    fn mul(self, scalar: f64) -> Desc {
-        let mut result = [0.0; 16];
-        for i in 0..16 {
-            result[i] = self.0[i] * scalar;
-        }
-        Desc(result)
+impl<T: Scalar, const N: usize> Mul<T> for Descriptor<T, N> {
+    type Output = Self;
+    fn mul(self, scalar: T) -> Self {
+        Descriptor(self.0.map(|x| x * scalar))
     }
 }
 
-impl Div<f64> for Desc {
-    type Output = Desc;
-    fn div(self, scalar: f64) -> Desc {
-        let mut result = [0.0; 16];
-        for i in 0..16 {
-            result[i] = self.0[i] / scalar;
-        }
-        Desc(result)
+impl<T: Scalar, const N: usize> Div<T> for Descriptor<T, N> {
+    type Output = Self;
+    fn div(self, scalar: T) -> Self {
+        Descriptor(self.0.map(|x| x / scalar))
     }
 }
 
 // cosine for Desc
-impl BitXor for Desc {
-    type Output = f64;
-    fn bitxor(self, other: Self) -> f64 {
-        let res = self.clone() * other.clone();
-        let norm1 = self.clone() * self.clone();
-        let norm2 = other.clone() * other.clone();
-        res / (norm1.sqrt() * norm2.sqrt())
+impl<T: Scalar, const N: usize> BitXor for Descriptor<T, N> {
+    type Output = T;
+    fn bitxor(self, other: Self) -> T {
+        self.cosine(&other)
     }
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Debug)]
+#[repr(C)]
 pub struct NodeType(usize);
 
 impl Type for NodeType {
@@ -123,34 +246,46 @@ impl NodeType {
     }
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
-pub struct Node {
+/// A typed node carrying an `N`-dimensional descriptor of `T` elements.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+#[serde(bound = "")]
+#[repr(C)]
+pub struct FeatureNode<T: Scalar, const N: usize> {
     id: usize,
     node_type: NodeType,
-    desc: Desc
+    desc: Descriptor<T, N>
 }
 
-impl BitXor for Node {
-    type Output = f64;
-    fn bitxor(self, other: Self) -> f64 {
-        return self.desc.clone() ^ other.desc.clone();
+// Written out because the derives would require `T: Eq + Hash`, which floats are not.
+impl<T: Scalar, const N: usize> Eq for FeatureNode<T, N> {}
+
+impl<T: Scalar, const N: usize> Hash for FeatureNode<T, N> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.node_type.hash(state);
+        self.desc.hash(state);
+    }
+}
+
+pub type Node = FeatureNode<f64, 16>;
+
+impl<T: Scalar, const N: usize> BitXor for FeatureNode<T, N> {
+    type Output = T;
+    fn bitxor(self, other: Self) -> T {
+        self.desc.cosine(&other.desc)
     }
 }
 
 fn generate_orthogonal_unit(base: &Desc) -> Desc {
-    let base_norm = (base.clone() * base.clone()).sqrt();
-    let mut orthogonal = Desc([0.0; 16]);
-    
+    let base_norm = base.norm();
     let mut rng = rng();
     loop {
         // 生成随机高斯向量
-        for i in 0..16 {
-            orthogonal.0[i] = rng.sample(StandardUniform);
-        }
+        let mut orthogonal = Desc::new(rng.sample(StandardUniform));
         
         
         // 计算与基向量的点积
-        let projection = (orthogonal.clone() * base.clone()) / base_norm;
+        let projection = orthogonal.dot(base) / base_norm;
         
         // 减去投影分量使其正交
         // for i in 0..16 {
@@ -160,30 +295,50 @@ fn generate_orthogonal_unit(base: &Desc) -> Desc {
         orthogonal = orthogonal - (base.clone() * projection) / base_norm;
         
         // 归一化处理
-        let ortho_norm = (orthogonal.clone() * orthogonal.clone()).sqrt();
+        let ortho_norm = orthogonal.norm();
         if ortho_norm > 1e-10 {
-            orthogonal = orthogonal / ortho_norm;
-            break;
+            return orthogonal / ortho_norm;
         }
     }
-    orthogonal
 }
 
 
+impl<T: Scalar, const N: usize> FeatureNode<T, N> {
+    pub fn new(id: usize, node_type: NodeType, desc: Descriptor<T, N>) -> Self {
+        FeatureNode {
+            id,
+            node_type,
+            desc
+        }
+    }
+
+    pub fn node_type(&self) -> &NodeType {
+        &self.node_type
+    }
+
+    pub fn desc(&self) -> &Descriptor<T, N> {
+        &self.desc
+    }
+
+    // A node with the default type and an all-zero descriptor, used when the source carries no descriptor.
+    pub fn from_id(id: usize) -> Self {
+        FeatureNode::new(id, NodeType::new(0), Descriptor::default())
+    }
+}
+
 impl Node {
     pub fn from_random(id: usize, k: usize, p: f64, alpha: f64, rng: &mut impl Rng) -> Node {
         // get A random [f64; 16]
         let random_type = rng.random_range(0..k);
         let desc = {
-            let random_vec: [f64; 16] = rng.sample(StandardUniform);
-            let desc = Desc(random_vec);
+            let desc = Desc::new(rng.sample(StandardUniform));
             
             if !clusters.read().unwrap().contains_key(&random_type) {
                 if clusters.read().unwrap().is_empty() {
                     clusters.write().unwrap().insert(random_type, desc.clone());
                     desc
                 } else {
-                    let avg_vec = clusters.read().unwrap().iter().map(|(_, v)| v.clone()).reduce(|a, b| a + b).unwrap();
+                    let avg_vec = clusters.read().unwrap().values().cloned().reduce(|a, b| a + b).unwrap();
                     let orthogonal = generate_orthogonal_unit(&avg_vec);
                     let res = orthogonal + desc;
                     clusters.write().unwrap().insert(random_type, res.clone());
@@ -194,66 +349,247 @@ impl Node {
                 let cluster_desc = cluster_guard.get(&random_type).unwrap().clone();
     
                 if rng.random_bool(p) {
-                    let res = cluster_desc.clone() * (1.0 - alpha) + desc * alpha;
-                    res
+                    cluster_desc.clone() * (1.0 - alpha) + desc * alpha
                 } else {
                     let orthogonal = generate_orthogonal_unit(&cluster_desc);
-                    let res = orthogonal * (1.0 - alpha) + desc * alpha;
-                    res
+                    orthogonal * (1.0 - alpha) + desc * alpha
                 }
             }
         };
 
-        Node {
-            id,
-            node_type: NodeType::new(random_type),
-            desc
-        }
+        Node::new(id, NodeType::new(random_type), desc)
     }
 }
 
-impl SingleId for Node {
+impl<T: Scalar, const N: usize> SingleId for FeatureNode<T, N> {
     fn id(&self) -> usize {
         self.id
     }
 }
 
-impl Display for Node {
+impl<T: Scalar, const N: usize> Display for FeatureNode<T, N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Node {{ id: {}, node_type: {} }}", self.id, self.node_type)
     }
 }
 
-impl Vertex for Node {}
+impl<T: Scalar, const N: usize> Vertex for FeatureNode<T, N> {}
 
+impl<T: Scalar, const N: usize> Type for FeatureNode<T, N> {
+    fn type_id(&self) -> usize {
+        self.node_type.type_id()
+    }
+}
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
-pub struct HyperedgeImpl {
+impl<T: Scalar, const N: usize> Embedding for Descriptor<T, N> {
+    type Scalar = T;
+    fn embedding(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: Scalar, const N: usize> Embedding for FeatureNode<T, N> {
+    type Scalar = T;
+    fn embedding(&self) -> &[T] {
+        self.desc.as_slice()
+    }
+}
+
+
+/// Marker types choosing the membership semantics of a `KindedHyperedge`.
+pub trait EdgeKind: Clone + Eq + Hash + Default + Debug + 'static {
+    const KIND: HyperedgeKind;
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct SetKind;
+
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct MultisetKind;
+
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct OrderedKind;
+
+impl EdgeKind for SetKind {
+    const KIND: HyperedgeKind = HyperedgeKind::Set;
+}
+
+impl EdgeKind for MultisetKind {
+    const KIND: HyperedgeKind = HyperedgeKind::Multiset;
+}
+
+impl EdgeKind for OrderedKind {
+    const KIND: HyperedgeKind = HyperedgeKind::Ordered;
+}
+
+/// An undirected hyperedge with a weight, a label and a type id. `from_nodes` gives weight 1, an empty label and type 0.
+///
+/// Members are stored once, as given; the kind `K` decides whether their order and repetitions matter. The sorted views
+/// used by set algebra borrow the members when they are already sorted and are built on demand otherwise.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(bound = "")]
+pub struct KindedHyperedge<K: EdgeKind> {
     nodes: Vec<usize>,
+    weight: f64,
+    label: String,
+    edge_type: usize,
+    #[serde(skip)]
+    kind: PhantomData<K>,
+}
+
+pub type HyperedgeImpl = KindedHyperedge<SetKind>;
+pub type MultisetHyperedge = KindedHyperedge<MultisetKind>;
+pub type OrderedHyperedge = KindedHyperedge<OrderedKind>;
+
+impl<K: EdgeKind> KindedHyperedge<K> {
+    pub fn new(nodes: Vec<usize>, weight: f64, label: String, edge_type: usize) -> Self {
+        KindedHyperedge { nodes, weight, label, edge_type, kind: PhantomData }
+    }
+
+    pub fn set_weight(&mut self, weight: f64) {
+        self.weight = weight;
+    }
+
+    pub fn set_label(&mut self, label: String) {
+        self.label = label;
+    }
+
+    pub fn set_type(&mut self, edge_type: usize) {
+        self.edge_type = edge_type;
+    }
+}
+
+// Weights are compared and hashed by bit pattern, like descriptors.
+impl<K: EdgeKind> PartialEq for KindedHyperedge<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes && self.weight.to_bits() == other.weight.to_bits() && self.label == other.label && self.edge_type == other.edge_type
+    }
+}
+
+impl<K: EdgeKind> Eq for KindedHyperedge<K> {}
+
+impl<K: EdgeKind> Hash for KindedHyperedge<K> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.nodes.hash(state);
+        self.weight.to_bits().hash(state);
+        self.label.hash(state);
+        self.edge_type.hash(state);
+    }
 }
 
-impl IdVector for HyperedgeImpl {
+impl<K: EdgeKind> IdVector for KindedHyperedge<K> {
     fn id(&self) -> Vec<usize> {
         self.nodes.clone()
     }
 }
 
-impl NodeSet for HyperedgeImpl {
+impl<K: EdgeKind> NodeSet for KindedHyperedge<K> {
     fn from_nodes(nodes: Vec<usize>) -> Self {
-        HyperedgeImpl { nodes }
+        KindedHyperedge::new(nodes, 1.0, String::new(), 0)
     }
 }
 
-impl Hyperedge for HyperedgeImpl {
+impl<K: EdgeKind> Type for KindedHyperedge<K> {
+    fn type_id(&self) -> usize {
+        self.edge_type
+    }
+}
+
+impl<K: EdgeKind> Weighted for KindedHyperedge<K> {
+    fn weight(&self) -> f64 {
+        self.weight
+    }
+}
+
+impl<K: EdgeKind> Label for KindedHyperedge<K> {
+    fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+impl<K: EdgeKind> Display for KindedHyperedge<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.nodes)?;
+        if !self.label.is_empty() {
+            write!(f, "[{}]", self.label)?;
+        }
+        Ok(())
+    }
+}
+
+impl<K: EdgeKind> Hyperedge for KindedHyperedge<K> {
     fn id_set(&self) -> HashSet<usize> {
         self.nodes.iter().cloned().collect()
     }
+
+    fn kind(&self) -> HyperedgeKind {
+        K::KIND
+    }
+
+    fn sorted_ids(&self) -> Cow<'_, [usize]> {
+        if self.nodes.windows(2).all(|w| w[0] < w[1]) {
+            return Cow::Borrowed(&self.nodes);
+        }
+        let mut ids = self.nodes.clone();
+        ids.sort_unstable();
+        ids.dedup();
+        Cow::Owned(ids)
+    }
+
+    fn sorted_members(&self) -> Cow<'_, [usize]> {
+        if self.nodes.is_sorted() {
+            return Cow::Borrowed(&self.nodes);
+        }
+        let mut ids = self.nodes.clone();
+        ids.sort_unstable();
+        Cow::Owned(ids)
+    }
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
+/// A directed hyperedge from `src` to `dst` with a weight, a label and a type id.
+#[derive(Serialize, Deserialize, Clone)]
 pub struct DirectedHyperedgeImpl {
     src: Vec<usize>,
     dst: Vec<usize>,
+    weight: f64,
+    label: String,
+    edge_type: usize,
+}
+
+impl DirectedHyperedgeImpl {
+    pub fn new(src: Vec<usize>, dst: Vec<usize>, weight: f64, label: String, edge_type: usize) -> Self {
+        DirectedHyperedgeImpl { src, dst, weight, label, edge_type }
+    }
+
+    pub fn set_weight(&mut self, weight: f64) {
+        self.weight = weight;
+    }
+
+    pub fn set_label(&mut self, label: String) {
+        self.label = label;
+    }
+
+    pub fn set_type(&mut self, edge_type: usize) {
+        self.edge_type = edge_type;
+    }
+}
+
+impl PartialEq for DirectedHyperedgeImpl {
+    fn eq(&self, other: &Self) -> bool {
+        self.src == other.src && self.dst == other.dst && self.weight.to_bits() == other.weight.to_bits() && self.label == other.label && self.edge_type == other.edge_type
+    }
+}
+
+impl Eq for DirectedHyperedgeImpl {}
+
+impl Hash for DirectedHyperedgeImpl {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.src.hash(state);
+        self.dst.hash(state);
+        self.weight.to_bits().hash(state);
+        self.label.hash(state);
+        self.edge_type.hash(state);
+    }
 }
 
 impl IdVector for DirectedHyperedgeImpl {
@@ -272,18 +608,59 @@ impl DirectedHyperedge for DirectedHyperedgeImpl {
     }
 }
 
+impl NodeSetPair for DirectedHyperedgeImpl {
+    type Node = Node;
+    fn from_nodes_pair(src: Vec<usize>, dst: Vec<usize>) -> Self {
+        DirectedHyperedgeImpl::new(src, dst, 1.0, String::new(), 0)
+    }
+}
+
+impl Type for DirectedHyperedgeImpl {
+    fn type_id(&self) -> usize {
+        self.edge_type
+    }
+}
+
+impl Weighted for DirectedHyperedgeImpl {
+    fn weight(&self) -> f64 {
+        self.weight
+    }
+}
+
+impl Label for DirectedHyperedgeImpl {
+    fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+impl Display for DirectedHyperedgeImpl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} -> {:?}", self.src, self.dst)?;
+        if !self.label.is_empty() {
+            write!(f, "[{}]", self.label)?;
+        }
+        Ok(())
+    }
+}
+
+/// A hypergraph over `FeatureNode<T, N>` whose hyperedges have kind `K`.
 #[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
-pub struct HypergraphImpl {
-    nodes: Vec<Node>,
-    edges: Vec<HyperedgeImpl>,
+#[serde(bound = "")]
+pub struct FeatureHypergraph<T: Scalar, const N: usize, K: EdgeKind = SetKind> {
+    nodes: Vec<FeatureNode<T, N>>,
+    edges: Vec<KindedHyperedge<K>>,
 }
 
-impl<'a> Hypergraph<'a> for HypergraphImpl {
-    type Node = Node;
-    type Edge = HyperedgeImpl;
+pub type HypergraphImpl = FeatureHypergraph<f64, 16>;
+pub type MultisetHypergraph = FeatureHypergraph<f64, 16, MultisetKind>;
+pub type OrderedHypergraph = FeatureHypergraph<f64, 16, OrderedKind>;
+
+impl<'a, T: Scalar, const N: usize, K: EdgeKind> Hypergraph<'a> for FeatureHypergraph<T, N, K> {
+    type Node = FeatureNode<T, N>;
+    type Edge = KindedHyperedge<K>;
 
     fn new() -> Self {
-        HypergraphImpl {
+        FeatureHypergraph {
             nodes: Vec::new(),
             edges: Vec::new(),
         }
@@ -307,21 +684,42 @@ impl<'a> Hypergraph<'a> for HypergraphImpl {
 }
 
 
+impl<'a, T: Scalar, const N: usize, K: EdgeKind> ToOwnedHypergraph<'a> for FeatureHypergraph<T, N, K> {
+    type Owned = Self;
+
+    fn to_owned_node(node: &Self::Node) -> Self::Node {
+        node.clone()
+    }
+
+    fn to_owned_edge(edge: &Self::Edge) -> Self::Edge {
+        edge.clone()
+    }
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
-pub struct DirectedHypergraphImpl {
-    nodes: Vec<Node>,
+#[serde(bound = "")]
+pub struct FeatureDirectedHypergraph<T: Scalar, const N: usize> {
+    nodes: Vec<FeatureNode<T, N>>,
     edges: Vec<DirectedHyperedgeImpl>,
 }
 
-impl DirectedHypergraphImpl {
-    pub fn new() -> Self {
-        DirectedHypergraphImpl {
+pub type DirectedHypergraphImpl = FeatureDirectedHypergraph<f64, 16>;
+
+impl<T: Scalar, const N: usize> Default for FeatureDirectedHypergraph<T, N> {
+    fn default() -> Self {
+        FeatureDirectedHypergraph {
             nodes: Vec::new(),
             edges: Vec::new(),
         }
     }
+}
 
-    pub fn add_node(&mut self, node: Node) {
+impl<T: Scalar, const N: usize> FeatureDirectedHypergraph<T, N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_node(&mut self, node: FeatureNode<T, N>) {
         self.nodes.push(node);
     }
 
@@ -329,3 +727,45 @@ impl DirectedHypergraphImpl {
         self.edges.push(edge);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn descriptors_of_any_dimension() {
+        let x = Descriptor::<f32, 768>::try_from(vec![0.5; 768]).unwrap();
+        let y = Descriptor::<f32, 768>::default();
+        assert_eq!(x.dim(), 768);
+        assert!((x.norm() - 768f32.sqrt() / 2.0).abs() < 1e-4);
+        assert!((x.normalize().norm() - 1.0).abs() < 1e-4);
+        assert_eq!(x.l2_distance(&y), x.norm());
+        assert_eq!(y.normalize(), y);
+        assert!(Descriptor::<f64, 3>::try_from(&[1.0, 2.0][..]).is_err());
+
+        let mut hypergraph = FeatureHypergraph::<f32, 768>::new();
+        hypergraph.add_node(FeatureNode::new(0, NodeType::new(1), x.clone()));
+        assert!((hypergraph.nodes().next().unwrap().desc().cosine(&x) - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn hyperedge_kinds() {
+        let (x, y) = (vec![1, 1, 2], vec![2, 1, 3, 1]);
+        let (sx, sy) = (HyperedgeImpl::from_nodes(x.clone()), HyperedgeImpl::from_nodes(y.clone()));
+        let (mx, my) = (MultisetHyperedge::from_nodes(x.clone()), MultisetHyperedge::from_nodes(y.clone()));
+        let (ox, oy) = (OrderedHyperedge::from_nodes(x.clone()), OrderedHyperedge::from_nodes(y.clone()));
+
+        assert!(sx.is_subset(&sy) && mx.is_subset(&my) && !ox.is_subset(&oy));
+        assert!(OrderedHyperedge::from_nodes(vec![1, 1]).is_subset(&oy));
+        assert!(!MultisetHyperedge::from_nodes(vec![1, 1, 1]).is_subset(&my));
+        assert!(sx.is_equal(&HyperedgeImpl::from_nodes(vec![2, 1])));
+        assert!(!mx.is_equal(&MultisetHyperedge::from_nodes(vec![2, 1])));
+        assert!(mx.is_equal(&MultisetHyperedge::from_nodes(vec![2, 1, 1])));
+        assert!(!ox.is_equal(&OrderedHyperedge::from_nodes(vec![2, 1, 1])));
+        assert!(ox.has_intersection(&oy) && !MultisetHyperedge::from_nodes(vec![7, 7]).is_singleton());
+
+        let mut hypergraph = MultisetHypergraph::new();
+        hypergraph.add_hyperedge(my);
+        assert_eq!(hypergraph.hyperedges().next().unwrap().multiplicities()[&1], 2);
+    }
+}
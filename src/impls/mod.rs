@@ -1,2 +1,7 @@
 pub mod standard;
-pub mod hypergraph;
\ No newline at end of file
+pub mod hypergraph;
+pub mod csr;
+pub mod property;
+pub mod multilabel;
+pub mod similarity;
+pub mod bitset;
\ No newline at end of file
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::impls::standard::{LabelNode, LabeledEdge};
+use crate::interfaces::graph::{Adjacency, AdjacencyInv, Directed, Graph, IdPair, SingleId};
+use crate::interfaces::labeled::{EdgeLabeled, Label, LabelIndex, LabelStatistics, Labeled, LabeledAdjacency, MultiLabeled};
+
+/// A set of labels, kept sorted by label text so that equal sets compare and hash equally.
+///
+/// `label()` joins the labels with `:`, escaping `:` and `\` inside them, so distinct sets never share a joined label.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub struct LabelSet<L: Label> {
+    labels: Vec<L>,
+    joined: String,
+}
+
+impl<L: Label> LabelSet<L> {
+    pub fn new(labels: impl IntoIterator<Item = L>) -> Self {
+        let mut labels = labels.into_iter().collect::<Vec<_>>();
+        labels.sort_by(|a, b| a.label().cmp(b.label()));
+        labels.dedup();
+        let joined = labels.iter().map(|l| l.label().replace('\\', "\\\\").replace(':', "\\:")).collect::<Vec<_>>().join(":");
+        LabelSet { labels, joined }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &L> {
+        self.labels.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn contains(&self, label: &L) -> bool {
+        self.labels.binary_search_by(|l| l.label().cmp(label.label())).is_ok()
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.labels.iter().all(|label| other.contains(label))
+    }
+}
+
+impl<L: Label> Display for LabelSet<L> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.joined)
+    }
+}
+
+impl<L: Label> Label for LabelSet<L> {
+    fn label(&self) -> &str {
+        &self.joined
+    }
+}
+
+/// A directed graph whose nodes carry a set of labels (`L1`) and whose edges carry one label (`L2`).
+///
+/// A data node matches a pattern node when it holds every label of the pattern. An inverted index maps each label to its nodes.
+pub struct MultiLabelGraph<L1: Label, L2: Label> {
+    nodes: Vec<LabelNode<LabelSet<L1>>>,
+    edges: Vec<LabeledEdge<L2>>,
+    label_index: HashMap<L1, Vec<usize>>,
+    statistics: LabelIndex,
+}
+
+impl<L1: Label, L2: Label> Default for MultiLabelGraph<L1, L2> {
+    fn default() -> Self {
+        MultiLabelGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            label_index: HashMap::new(),
+            statistics: LabelIndex::new(),
+        }
+    }
+}
+
+impl<'a, L1: Label, L2: Label> Graph<'a> for MultiLabelGraph<L1, L2> {
+    type Node = LabelNode<LabelSet<L1>>;
+    type Edge = LabeledEdge<L2>;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn nodes(&'a self) -> impl Iterator<Item = &'a Self::Node> {
+        self.nodes.iter()
+    }
+
+    fn edges(&'a self) -> impl Iterator<Item = &'a Self::Edge> {
+        self.edges.iter()
+    }
+
+    fn add_node(&mut self, node: Self::Node) {
+        for label in node.label_value().iter() {
+            self.label_index.entry(label.clone()).or_default().push(self.nodes.len());
+        }
+        self.statistics.add_node_labels(node.id(), node.label_value().iter().map(|label| label.label()));
+        self.nodes.push(node);
+    }
+
+    fn add_edge(&mut self, edge: Self::Edge) {
+        let (src, dst) = edge.pair();
+        self.statistics.add_edge(src, edge.label_value().label(), dst);
+        self.edges.push(edge);
+    }
+}
+
+impl<'a, L1: Label, L2: Label> Labeled<'a> for MultiLabelGraph<L1, L2> {
+    fn label_same(&self, node: &Self::Node, label: &Self::Node) -> bool {
+        label.label_value().is_subset(node.label_value())
+    }
+
+    fn get_label(&'a self, node: &'a Self::Node) -> &'a impl Label {
+        node.label_value()
+    }
+
+    fn get_edges_pair_label(&'a self) -> impl Iterator<Item = (&'a Self::Node, &'a Self::Node, &'a impl Label)> {
+        let id_map: HashMap<_, _> = HashMap::from_iter(self.nodes.iter().map(|node| (node.id(), node)));
+        self.edges.iter().map(move |edge| (id_map[&edge.pair().0], id_map[&edge.pair().1], edge.label_value())).collect::<Vec<_>>().into_iter()
+    }
+
+    fn edge_label_same(&self, edge1: &Self::Edge, edge2: &Self::Edge) -> bool {
+        edge1.label_value() == edge2.label_value()
+    }
+
+    fn edge_node_label_same(&self, src1: &Self::Node, edge1: &Self::Edge, dst1: &Self::Node, src2: &Self::Node, edge2: &Self::Edge, dst2: &Self::Node) -> bool {
+        self.label_same(src1, src2) && self.edge_label_same(edge1, edge2) && self.label_same(dst1, dst2)
+    }
+}
+
+impl<'a, L1: Label, L2: Label> EdgeLabeled<'a> for MultiLabelGraph<L1, L2> {
+    fn get_edge_label(&'a self, edge: &'a Self::Edge) -> &'a impl Label {
+        edge.label_value()
+    }
+}
+
+impl<'a, L1: Label, L2: Label> LabelStatistics<'a> for MultiLabelGraph<L1, L2> {
+    fn label_index(&self) -> &LabelIndex {
+        &self.statistics
+    }
+
+    fn node_at(&'a self, position: usize) -> &'a Self::Node {
+        &self.nodes[position]
+    }
+
+    fn edge_at(&'a self, position: usize) -> &'a Self::Edge {
+        &self.edges[position]
+    }
+}
+
+impl<'a, L1: Label, L2: Label> MultiLabeled<'a> for MultiLabelGraph<L1, L2> {
+    type L = L1;
+
+    fn node_labels(&'a self, node: &'a Self::Node) -> impl Iterator<Item = &'a Self::L> {
+        node.label_value().iter()
+    }
+
+    fn nodes_with_label(&'a self, label: &Self::L) -> impl Iterator<Item = &'a Self::Node> {
+        self.label_index.get(label).into_iter().flatten().map(|&i| &self.nodes[i])
+    }
+
+    fn label_count(&'a self, label: &Self::L) -> usize {
+        self.label_index.get(label).map_or(0, Vec::len)
+    }
+}
+
+impl<L1: Label, L2: Label> Directed for MultiLabelGraph<L1, L2> {}
+
+impl<L1: Label, L2: Label> Adjacency<'_> for MultiLabelGraph<L1, L2> {}
+
+impl<L1: Label, L2: Label> AdjacencyInv<'_> for MultiLabelGraph<L1, L2> {}
+
+impl<L1: Label, L2: Label> LabeledAdjacency<'_> for MultiLabelGraph<L1, L2> {}
+
+impl<L1: Label, L2: Label> MultiLabelGraph<L1, L2> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_node(&mut self, id: u64, labels: impl IntoIterator<Item = L1>) {
+        Graph::add_node(self, LabelNode::new(id, LabelSet::new(labels)));
+    }
+
+    pub fn add_edge(&mut self, src: u64, dst: u64, label: L2) {
+        Graph::add_edge(self, LabeledEdge::new(src, dst, label));
+    }
+}
+
+impl<L1: Label, L2: Label> Display for MultiLabelGraph<L1, L2> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = String::new();
+        s.push_str("nodes: ");
+        for node in self.nodes.iter() {
+            s.push_str(format!("{}, ", node).as_str());
+        }
+        s.push_str("\nedges: ");
+        for edge in self.edges.iter() {
+            s.push_str(format!("{}, ", edge).as_str());
+        }
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> MultiLabelGraph<String, String> {
+        let mut graph = MultiLabelGraph::new();
+        graph.add_node(0, ["person".to_string(), "admin".to_string()]);
+        graph.add_node(1, ["person".to_string()]);
+        graph.add_node(2, ["person".to_string(), "admin".to_string(), "owner".to_string()]);
+        graph.add_node(3, ["company".to_string()]);
+        graph.add_edge(0, 3, "works_at".to_string());
+        graph
+    }
+
+    #[test]
+    fn patterns_match_by_containment() {
+        let graph = graph();
+        let mut pattern = MultiLabelGraph::<String, String>::new();
+        pattern.add_node(9, ["admin".to_string(), "person".to_string()]);
+        let admin = pattern.nodes().next().unwrap();
+        let nodes = graph.nodes().collect::<Vec<_>>();
+
+        assert!(graph.label_same(nodes[0], admin));
+        assert!(graph.label_same(nodes[2], admin));
+        assert!(!graph.label_same(nodes[1], admin));
+        assert!(!graph.label_same(admin, nodes[2]));
+    }
+
+    #[test]
+    fn inverted_index_and_candidates() {
+        let graph = graph();
+        let ids = |nodes: Vec<&LabelNode<LabelSet<String>>>| nodes.into_iter().map(|n| n.id()).collect::<Vec<_>>();
+        assert_eq!(ids(graph.nodes_with_label(&"person".to_string()).collect()), vec![0, 1, 2]);
+        assert_eq!(ids(graph.nodes_with_label(&"ghost".to_string()).collect()), Vec::<usize>::new());
+        assert_eq!(graph.label_count(&"admin".to_string()), 2);
+        assert_eq!(graph.label_count(&"owner".to_string()), 1);
+
+        let mut pattern = MultiLabelGraph::<String, String>::new();
+        pattern.add_node(7, ["person".to_string(), "admin".to_string()]);
+        pattern.add_node(8, Vec::<String>::new());
+        let nodes = pattern.nodes().collect::<Vec<_>>();
+        // Candidates come from the rarer `admin` label and are filtered by containment.
+        assert_eq!(ids(graph.candidates(nodes[0]).collect()), vec![0, 2]);
+        // A pattern without labels matches every node.
+        assert_eq!(ids(graph.candidates(nodes[1]).collect()), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn label_index_counts_each_label() {
+        let graph = graph();
+        let ids = |nodes: Vec<&LabelNode<LabelSet<String>>>| nodes.into_iter().map(|n| n.id()).collect::<Vec<_>>();
+        for label in ["person", "admin", "owner", "company", "ghost"] {
+            let expected = ids(graph.nodes_with_label(&label.to_string()).collect());
+            assert_eq!(ids(graph.nodes_labeled(label).collect()), expected);
+            assert_eq!(graph.label_index().node_label_frequency(label), expected.len());
+        }
+        let index = graph.label_index();
+        assert_eq!(index.node_count(), 4);
+        assert_eq!((index.pair_count("person", "company"), index.pair_count("admin", "company")), (1, 1));
+        assert_eq!(index.triple_count("admin", "works_at", "company"), 1);
+        assert_eq!(index.pair_count("owner", "company"), 0);
+        assert_eq!(index.rarest(["person", "owner"]), Some("owner"));
+    }
+
+    #[test]
+    fn joined_labels_do_not_collide() {
+        let one = LabelSet::new(["a:b".to_string()]);
+        let two = LabelSet::new(["a".to_string(), "b".to_string()]);
+        assert_ne!(one.label(), two.label());
+        assert_eq!(two.label(), "a:b");
+        assert_ne!(LabelSet::new(["a\\".to_string(), "b".to_string()]).label(), LabelSet::new(["a\\:b".to_string()]).label());
+    }
+}
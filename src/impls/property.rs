@@ -0,0 +1,596 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::ops::{Bound, RangeBounds};
+
+use crate::impls::standard::{LabelNode, LabeledEdge};
+use crate::interfaces::graph::{Adjacency, AdjacencyInv, Directed, Graph, IdPair, SingleId};
+use crate::interfaces::labeled::{EdgeLabeled, Label, LabelIndex, LabelStatistics, Labeled, LabeledAdjacency};
+
+/// A property value attached to a node or an edge.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    String(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    List(Vec<PropertyValue>),
+}
+
+impl PropertyValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            PropertyValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            PropertyValue::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    // Ints are widened, so numeric properties can be read as floats.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            PropertyValue::Float(f) => Some(*f),
+            PropertyValue::Int(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            PropertyValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[PropertyValue]> {
+        match self {
+            PropertyValue::List(l) => Some(l),
+            _ => None,
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            PropertyValue::Bool(_) => 0,
+            PropertyValue::Int(_) | PropertyValue::Float(_) => 1,
+            PropertyValue::String(_) => 2,
+            PropertyValue::List(_) => 3,
+        }
+    }
+
+    /// A total order used by the property indexes: values of different kinds are ordered bool < number < string < list,
+    /// and lists compare lexicographically. Ints and floats compare by exact numeric value, so `Int(2)` equals `Float(2.0)`;
+    /// `-0.0` equals `0.0`, and NaNs sort after every number (before it when negative), as in `f64::total_cmp`.
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (PropertyValue::Bool(a), PropertyValue::Bool(b)) => a.cmp(b),
+            (PropertyValue::Int(a), PropertyValue::Int(b)) => a.cmp(b),
+            (PropertyValue::Float(a), PropertyValue::Float(b)) => a.partial_cmp(b).unwrap_or_else(|| a.total_cmp(b)),
+            (PropertyValue::Int(a), PropertyValue::Float(b)) => cmp_int_float(*a, *b),
+            (PropertyValue::Float(a), PropertyValue::Int(b)) => cmp_int_float(*b, *a).reverse(),
+            (PropertyValue::String(a), PropertyValue::String(b)) => a.cmp(b),
+            (PropertyValue::List(a), PropertyValue::List(b)) => {
+                a.iter().zip(b.iter()).map(|(x, y)| x.total_cmp(y)).find(|o| o.is_ne()).unwrap_or(a.len().cmp(&b.len()))
+            }
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+// Compare without rounding: `i as f64` is inexact beyond 2^53 and would make the order intransitive.
+fn cmp_int_float(i: i64, f: f64) -> Ordering {
+    if f.is_nan() {
+        return if f.is_sign_negative() { Ordering::Greater } else { Ordering::Less };
+    }
+    // 2^63 is exactly representable; every float at or above it exceeds `i64::MAX`.
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+    if f >= LIMIT {
+        return Ordering::Less;
+    }
+    if f < -LIMIT {
+        return Ordering::Greater;
+    }
+    let whole = f.trunc();
+    i.cmp(&(whole as i64)).then_with(|| 0.0.partial_cmp(&(f - whole)).unwrap())
+}
+
+impl Display for PropertyValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropertyValue::String(s) => write!(f, "{:?}", s),
+            PropertyValue::Int(i) => write!(f, "{}", i),
+            PropertyValue::Float(x) => write!(f, "{}", x),
+            PropertyValue::Bool(b) => write!(f, "{}", b),
+            PropertyValue::List(l) => {
+                write!(f, "[")?;
+                for (i, v) in l.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", v)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl From<String> for PropertyValue {
+    fn from(value: String) -> Self {
+        PropertyValue::String(value)
+    }
+}
+
+impl From<&str> for PropertyValue {
+    fn from(value: &str) -> Self {
+        PropertyValue::String(value.to_string())
+    }
+}
+
+impl From<i64> for PropertyValue {
+    fn from(value: i64) -> Self {
+        PropertyValue::Int(value)
+    }
+}
+
+impl From<f64> for PropertyValue {
+    fn from(value: f64) -> Self {
+        PropertyValue::Float(value)
+    }
+}
+
+impl From<bool> for PropertyValue {
+    fn from(value: bool) -> Self {
+        PropertyValue::Bool(value)
+    }
+}
+
+impl From<Vec<PropertyValue>> for PropertyValue {
+    fn from(value: Vec<PropertyValue>) -> Self {
+        PropertyValue::List(value)
+    }
+}
+
+pub type Properties = HashMap<String, PropertyValue>;
+
+// Index key ordered by `PropertyValue::total_cmp`.
+#[derive(Clone, Debug)]
+struct Ordered(PropertyValue);
+
+impl PartialEq for Ordered {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Ordered {}
+
+impl PartialOrd for Ordered {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ordered {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+fn ordered_bound(bound: Bound<&PropertyValue>) -> Bound<Ordered> {
+    match bound {
+        Bound::Included(v) => Bound::Included(Ordered(v.clone())),
+        Bound::Excluded(v) => Bound::Excluded(Ordered(v.clone())),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+fn in_range(value: &PropertyValue, range: &impl RangeBounds<PropertyValue>) -> bool {
+    let lower = match range.start_bound() {
+        Bound::Included(v) => value.total_cmp(v).is_ge(),
+        Bound::Excluded(v) => value.total_cmp(v).is_gt(),
+        Bound::Unbounded => true,
+    };
+    let upper = match range.end_bound() {
+        Bound::Included(v) => value.total_cmp(v).is_le(),
+        Bound::Excluded(v) => value.total_cmp(v).is_lt(),
+        Bound::Unbounded => true,
+    };
+    lower && upper
+}
+
+/// An ordered index from the values of one property to the elements holding them.
+#[derive(Default)]
+struct PropertyIndex(BTreeMap<Ordered, Vec<usize>>);
+
+impl PropertyIndex {
+    fn insert(&mut self, value: &PropertyValue, element: usize) {
+        self.0.entry(Ordered(value.clone())).or_default().push(element);
+    }
+
+    fn remove(&mut self, value: &PropertyValue, element: usize) {
+        let key = Ordered(value.clone());
+        if let Some(elements) = self.0.get_mut(&key) {
+            elements.retain(|&e| e != element);
+            if elements.is_empty() {
+                self.0.remove(&key);
+            }
+        }
+    }
+
+    fn equal(&self, value: &PropertyValue) -> Vec<usize> {
+        self.0.get(&Ordered(value.clone())).cloned().unwrap_or_default()
+    }
+
+    fn range(&self, range: &impl RangeBounds<PropertyValue>) -> Vec<usize> {
+        let bounds = (ordered_bound(range.start_bound()), ordered_bound(range.end_bound()));
+        self.0.range(bounds).flat_map(|(_, elements)| elements.iter().copied()).collect()
+    }
+}
+
+// Properties and indexes for one kind of element (nodes by id, edges by position).
+#[derive(Default)]
+struct PropertyStore {
+    properties: HashMap<usize, Properties>,
+    indexes: HashMap<String, PropertyIndex>,
+}
+
+impl PropertyStore {
+    fn get(&self, element: usize, key: &str) -> Option<&PropertyValue> {
+        self.properties.get(&element).and_then(|props| props.get(key))
+    }
+
+    fn set(&mut self, element: usize, key: &str, value: PropertyValue) -> Option<PropertyValue> {
+        let old = self.remove(element, key);
+        if let Some(index) = self.indexes.get_mut(key) {
+            index.insert(&value, element);
+        }
+        self.properties.entry(element).or_default().insert(key.to_string(), value);
+        old
+    }
+
+    fn remove(&mut self, element: usize, key: &str) -> Option<PropertyValue> {
+        let old = self.properties.get_mut(&element).and_then(|props| props.remove(key));
+        if let (Some(old), Some(index)) = (&old, self.indexes.get_mut(key)) {
+            index.remove(old, element);
+        }
+        old
+    }
+
+    fn create_index(&mut self, key: &str) {
+        let mut index = PropertyIndex::default();
+        for (&element, props) in self.properties.iter() {
+            if let Some(value) = props.get(key) {
+                index.insert(value, element);
+            }
+        }
+        self.indexes.insert(key.to_string(), index);
+    }
+
+    fn equal(&self, key: &str, value: &PropertyValue) -> Vec<usize> {
+        match self.indexes.get(key) {
+            Some(index) => index.equal(value),
+            None => self.scan(key, |v| v.total_cmp(value).is_eq()),
+        }
+    }
+
+    fn range(&self, key: &str, range: &impl RangeBounds<PropertyValue>) -> Vec<usize> {
+        match self.indexes.get(key) {
+            Some(index) => index.range(range),
+            None => self.scan(key, |v| in_range(v, range)),
+        }
+    }
+
+    fn scan(&self, key: &str, pred: impl Fn(&PropertyValue) -> bool) -> Vec<usize> {
+        let mut elements = self.properties.iter()
+            .filter(|(_, props)| props.get(key).is_some_and(&pred))
+            .map(|(&element, _)| element)
+            .collect::<Vec<_>>();
+        elements.sort();
+        elements
+    }
+}
+
+/// A directed property graph: `LabelNode`s and `LabeledEdge`s, each of which may carry a map of properties.
+///
+/// Node properties are keyed by node id and edge properties by the position of the edge in `edges()`.
+/// Indexes created with `create_node_index` / `create_edge_index` are kept up to date by the setters.
+pub struct PropertyGraph<L1: Label, L2: Label> {
+    nodes: Vec<LabelNode<L1>>,
+    edges: Vec<LabeledEdge<L2>>,
+    statistics: LabelIndex,
+    node_properties: PropertyStore,
+    edge_properties: PropertyStore,
+}
+
+impl<L1: Label, L2: Label> Default for PropertyGraph<L1, L2> {
+    fn default() -> Self {
+        PropertyGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            statistics: LabelIndex::new(),
+            node_properties: PropertyStore::default(),
+            edge_properties: PropertyStore::default(),
+        }
+    }
+}
+
+impl<'a, L1: Label, L2: Label> Graph<'a> for PropertyGraph<L1, L2> {
+    type Node = LabelNode<L1>;
+    type Edge = LabeledEdge<L2>;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn nodes(&'a self) -> impl Iterator<Item = &'a Self::Node> {
+        self.nodes.iter()
+    }
+
+    fn edges(&'a self) -> impl Iterator<Item = &'a Self::Edge> {
+        self.edges.iter()
+    }
+
+    fn add_node(&mut self, node: Self::Node) {
+        self.statistics.add_node(node.id(), node.label());
+        self.nodes.push(node);
+    }
+
+    fn add_edge(&mut self, edge: Self::Edge) {
+        let (src, dst) = edge.pair();
+        self.statistics.add_edge(src, edge.label(), dst);
+        self.edges.push(edge);
+    }
+}
+
+impl<'a, L1: Label, L2: Label> Labeled<'a> for PropertyGraph<L1, L2> {
+    fn label_same(&self, node: &Self::Node, label: &Self::Node) -> bool {
+        node.label() == label.label()
+    }
+
+    fn get_label(&'a self, node: &'a Self::Node) -> &'a impl Label {
+        node
+    }
+
+    fn get_edges_pair_label(&'a self) -> impl Iterator<Item = (&'a Self::Node, &'a Self::Node, &'a impl Label)> {
+        let id_map: HashMap<_, _> = HashMap::from_iter(self.nodes.iter().map(|node| (node.id(), node)));
+        self.edges.iter().map(move |edge| (id_map[&edge.pair().0], id_map[&edge.pair().1], edge)).collect::<Vec<_>>().into_iter()
+    }
+
+    fn edge_label_same(&self, edge1: &Self::Edge, edge2: &Self::Edge) -> bool {
+        edge1.label() == edge2.label()
+    }
+
+    fn edge_node_label_same(&self, src1: &Self::Node, edge1: &Self::Edge, dst1: &Self::Node, src2: &Self::Node, edge2: &Self::Edge, dst2: &Self::Node) -> bool {
+        self.label_same(src1, src2) && self.edge_label_same(edge1, edge2) && self.label_same(dst1, dst2)
+    }
+}
+
+impl<'a, L1: Label, L2: Label> EdgeLabeled<'a> for PropertyGraph<L1, L2> {
+    fn get_edge_label(&'a self, edge: &'a Self::Edge) -> &'a impl Label {
+        edge.label_value()
+    }
+}
+
+impl<'a, L1: Label, L2: Label> LabelStatistics<'a> for PropertyGraph<L1, L2> {
+    fn label_index(&self) -> &LabelIndex {
+        &self.statistics
+    }
+
+    fn node_at(&'a self, position: usize) -> &'a Self::Node {
+        &self.nodes[position]
+    }
+
+    fn edge_at(&'a self, position: usize) -> &'a Self::Edge {
+        &self.edges[position]
+    }
+}
+
+impl<L1: Label, L2: Label> Directed for PropertyGraph<L1, L2> {}
+
+impl<L1: Label, L2: Label> Adjacency<'_> for PropertyGraph<L1, L2> {}
+
+impl<L1: Label, L2: Label> AdjacencyInv<'_> for PropertyGraph<L1, L2> {}
+
+impl<L1: Label, L2: Label> LabeledAdjacency<'_> for PropertyGraph<L1, L2> {}
+
+impl<L1: Label, L2: Label> PropertyGraph<L1, L2> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_node(&mut self, id: u64, label: L1, properties: Properties) {
+        Graph::add_node(self, LabelNode::new(id, label));
+        for (key, value) in properties {
+            self.node_properties.set(id as usize, &key, value);
+        }
+    }
+
+    /// Add an edge and return its position, which identifies it for edge properties.
+    pub fn add_edge(&mut self, src: u64, dst: u64, label: L2, properties: Properties) -> usize {
+        let edge = self.edges.len();
+        Graph::add_edge(self, LabeledEdge::new(src, dst, label));
+        for (key, value) in properties {
+            self.edge_properties.set(edge, &key, value);
+        }
+        edge
+    }
+
+    pub fn edge_at(&self, edge: usize) -> &LabeledEdge<L2> {
+        &self.edges[edge]
+    }
+
+    pub fn node_properties(&self, node: &LabelNode<L1>) -> Option<&Properties> {
+        self.node_properties.properties.get(&node.id())
+    }
+
+    pub fn edge_properties(&self, edge: usize) -> Option<&Properties> {
+        self.edge_properties.properties.get(&edge)
+    }
+
+    pub fn node_property(&self, node: &LabelNode<L1>, key: &str) -> Option<&PropertyValue> {
+        self.node_properties.get(node.id(), key)
+    }
+
+    pub fn edge_property(&self, edge: usize, key: &str) -> Option<&PropertyValue> {
+        self.edge_properties.get(edge, key)
+    }
+
+    pub fn node_str(&self, node: &LabelNode<L1>, key: &str) -> Option<&str> {
+        self.node_property(node, key).and_then(PropertyValue::as_str)
+    }
+
+    pub fn node_int(&self, node: &LabelNode<L1>, key: &str) -> Option<i64> {
+        self.node_property(node, key).and_then(PropertyValue::as_int)
+    }
+
+    pub fn node_float(&self, node: &LabelNode<L1>, key: &str) -> Option<f64> {
+        self.node_property(node, key).and_then(PropertyValue::as_float)
+    }
+
+    pub fn node_bool(&self, node: &LabelNode<L1>, key: &str) -> Option<bool> {
+        self.node_property(node, key).and_then(PropertyValue::as_bool)
+    }
+
+    pub fn node_list(&self, node: &LabelNode<L1>, key: &str) -> Option<&[PropertyValue]> {
+        self.node_property(node, key).and_then(PropertyValue::as_list)
+    }
+
+    pub fn edge_str(&self, edge: usize, key: &str) -> Option<&str> {
+        self.edge_property(edge, key).and_then(PropertyValue::as_str)
+    }
+
+    pub fn edge_int(&self, edge: usize, key: &str) -> Option<i64> {
+        self.edge_property(edge, key).and_then(PropertyValue::as_int)
+    }
+
+    pub fn edge_float(&self, edge: usize, key: &str) -> Option<f64> {
+        self.edge_property(edge, key).and_then(PropertyValue::as_float)
+    }
+
+    pub fn edge_bool(&self, edge: usize, key: &str) -> Option<bool> {
+        self.edge_property(edge, key).and_then(PropertyValue::as_bool)
+    }
+
+    pub fn edge_list(&self, edge: usize, key: &str) -> Option<&[PropertyValue]> {
+        self.edge_property(edge, key).and_then(PropertyValue::as_list)
+    }
+
+    /// Set a node property and return the previous value.
+    pub fn set_node_property(&mut self, node: &LabelNode<L1>, key: &str, value: impl Into<PropertyValue>) -> Option<PropertyValue> {
+        self.node_properties.set(node.id(), key, value.into())
+    }
+
+    /// Set an edge property and return the previous value.
+    pub fn set_edge_property(&mut self, edge: usize, key: &str, value: impl Into<PropertyValue>) -> Option<PropertyValue> {
+        self.edge_properties.set(edge, key, value.into())
+    }
+
+    pub fn remove_node_property(&mut self, node: &LabelNode<L1>, key: &str) -> Option<PropertyValue> {
+        self.node_properties.remove(node.id(), key)
+    }
+
+    pub fn remove_edge_property(&mut self, edge: usize, key: &str) -> Option<PropertyValue> {
+        self.edge_properties.remove(edge, key)
+    }
+
+    pub fn create_node_index(&mut self, key: &str) {
+        self.node_properties.create_index(key);
+    }
+
+    pub fn create_edge_index(&mut self, key: &str) {
+        self.edge_properties.create_index(key);
+    }
+
+    fn nodes_by_id(&self, ids: Vec<usize>) -> impl Iterator<Item = &LabelNode<L1>> {
+        let ids = ids.into_iter().collect::<std::collections::HashSet<_>>();
+        self.nodes.iter().filter(move |node| ids.contains(&node.id()))
+    }
+
+    /// Nodes whose property `key` equals `value`, answered from the index on `key` if there is one.
+    pub fn nodes_where(&self, key: &str, value: &PropertyValue) -> impl Iterator<Item = &LabelNode<L1>> {
+        self.nodes_by_id(self.node_properties.equal(key, value))
+    }
+
+    /// Nodes whose property `key` lies in `range`, compared with `PropertyValue::total_cmp`.
+    pub fn nodes_in_range(&self, key: &str, range: impl RangeBounds<PropertyValue>) -> impl Iterator<Item = &LabelNode<L1>> {
+        self.nodes_by_id(self.node_properties.range(key, &range))
+    }
+
+    /// Positions of the edges whose property `key` equals `value`.
+    pub fn edges_where(&self, key: &str, value: &PropertyValue) -> Vec<usize> {
+        self.edge_properties.equal(key, value)
+    }
+
+    /// Positions of the edges whose property `key` lies in `range`.
+    pub fn edges_in_range(&self, key: &str, range: impl RangeBounds<PropertyValue>) -> Vec<usize> {
+        self.edge_properties.range(key, &range)
+    }
+
+    pub fn filter_nodes(&self, pred: impl Fn(&LabelNode<L1>, &Properties) -> bool) -> impl Iterator<Item = &LabelNode<L1>> {
+        let empty = Properties::new();
+        self.nodes.iter().filter(move |node| pred(node, self.node_properties(node).unwrap_or(&empty)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexed_and_scanned_lookups_agree() {
+        let mut graph = PropertyGraph::<String, String>::new();
+        for (id, age) in [(1, 30), (2, 41), (3, 25), (4, 41)] {
+            graph.add_node(id, "person".to_string(), Properties::from([("age".to_string(), PropertyValue::Int(age))]));
+        }
+        let edge = graph.add_edge(1, 2, "knows".to_string(), Properties::from([("since".to_string(), 2010.into())]));
+        assert_eq!(graph.edge_int(edge, "since"), Some(2010));
+
+        let ids = |nodes: Vec<&LabelNode<String>>| nodes.into_iter().map(|n| n.id()).collect::<Vec<_>>();
+        let scanned = ids(graph.nodes_in_range("age", PropertyValue::Int(26)..PropertyValue::Int(41)).collect());
+        graph.create_node_index("age");
+        let indexed = ids(graph.nodes_in_range("age", PropertyValue::Int(26)..PropertyValue::Int(41)).collect());
+        assert_eq!(scanned, vec![1]);
+        assert_eq!(indexed, scanned);
+
+        let third = graph.nodes().nth(2).unwrap().clone();
+        graph.set_node_property(&third, "age", 41);
+        assert_eq!(ids(graph.nodes_where("age", &PropertyValue::Int(41)).collect()), vec![2, 3, 4]);
+        assert_eq!(graph.node_int(&third, "age"), Some(41));
+    }
+
+    #[test]
+    fn ints_and_floats_compare_by_value() {
+        use PropertyValue::{Float, Int};
+        assert_eq!(Int(2).total_cmp(&Float(2.5)), Ordering::Less);
+        assert_eq!(Float(-2.5).total_cmp(&Int(-2)), Ordering::Less);
+        assert_eq!(Int(3).total_cmp(&Float(3.0)), Ordering::Equal);
+        assert_eq!(Float(-0.0).total_cmp(&Int(0)), Ordering::Equal);
+        assert_eq!(Int(i64::MAX).total_cmp(&Float(9_223_372_036_854_775_808.0)), Ordering::Less);
+        assert_eq!(Int(1 << 53 | 1).total_cmp(&Float((1u64 << 53) as f64)), Ordering::Greater);
+        assert_eq!(Int(i64::MIN).total_cmp(&Float(f64::NAN)), Ordering::Less);
+        assert_eq!(Float(2.0).total_cmp(&PropertyValue::from("2")), Ordering::Less);
+
+        let mut graph = PropertyGraph::<String, String>::new();
+        let values = [Int(1), Float(1.5), Int(2), Float(2.0), Float(3.5)];
+        for (id, value) in values.into_iter().enumerate() {
+            graph.add_node(id as u64, "x".to_string(), Properties::from([("v".to_string(), value)]));
+        }
+        let ids = |nodes: Vec<&LabelNode<String>>| nodes.into_iter().map(|n| n.id()).collect::<Vec<_>>();
+        for indexed in [false, true] {
+            if indexed {
+                graph.create_node_index("v");
+            }
+            assert_eq!(ids(graph.nodes_in_range("v", Float(1.2)..=Float(2.0)).collect()), vec![1, 2, 3]);
+            assert_eq!(ids(graph.nodes_in_range("v", Int(2)..).collect()), vec![2, 3, 4]);
+            assert_eq!(ids(graph.nodes_where("v", &Float(1.0)).collect()), vec![0]);
+            assert_eq!(ids(graph.nodes_where("v", &Int(2)).collect()), vec![2, 3]);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::hash::Hash;
+
+use crate::impls::hypergraph::{Descriptor, Scalar};
+use crate::interfaces::labeled::Label;
+use crate::interfaces::similarity::{Embedding, LabelSimilarity};
+
+/// Labels are similar when they are equal. This is the default comparator of `HyperLabelGraph`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Exact;
+
+impl<L: Label> LabelSimilarity<L> for Exact {
+    fn similar(&self, x: &L, y: &L) -> bool {
+        x == y
+    }
+}
+
+/// Labels are similar when their text is equal ignoring case.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CaseInsensitive;
+
+impl<L: Label> LabelSimilarity<L> for CaseInsensitive {
+    fn similar(&self, x: &L, y: &L) -> bool {
+        x.label().to_lowercase() == y.label().to_lowercase()
+    }
+}
+
+/// Labels are similar when their Levenshtein distance (in characters) is at most `max_distance`.
+#[derive(Clone, Copy, Debug)]
+pub struct EditDistance {
+    pub max_distance: usize,
+}
+
+impl EditDistance {
+    pub fn new(max_distance: usize) -> Self {
+        EditDistance { max_distance }
+    }
+}
+
+impl<L: Label> LabelSimilarity<L> for EditDistance {
+    fn similar(&self, x: &L, y: &L) -> bool {
+        levenshtein(x.label(), y.label()) <= self.max_distance
+    }
+}
+
+/// Labels are similar when the Jaccard index of their lower-cased word sets is at least `threshold`.
+#[derive(Clone, Copy, Debug)]
+pub struct Jaccard {
+    pub threshold: f64,
+}
+
+impl Jaccard {
+    pub fn new(threshold: f64) -> Self {
+        Jaccard { threshold }
+    }
+}
+
+impl<L: Label> LabelSimilarity<L> for Jaccard {
+    fn similar(&self, x: &L, y: &L) -> bool {
+        jaccard(x.label(), y.label()) >= self.threshold
+    }
+}
+
+/// Values are similar when the cosine of their descriptor embeddings is at least `threshold`.
+#[derive(Clone, Copy, Debug)]
+pub struct CosineThreshold {
+    pub threshold: f64,
+}
+
+impl CosineThreshold {
+    pub fn new(threshold: f64) -> Self {
+        CosineThreshold { threshold }
+    }
+}
+
+// A zero descriptor has no direction, so its cosine is NaN and it is similar to nothing.
+impl<L: Embedding> LabelSimilarity<L> for CosineThreshold {
+    fn similar(&self, x: &L, y: &L) -> bool {
+        cosine(x.embedding(), y.embedding()) >= self.threshold
+    }
+}
+
+/// A label text together with a descriptor embedding, so that `HyperLabelGraph` can compare labels with `CosineThreshold`.
+///
+/// Equality and hashing use both the text and the descriptor.
+#[derive(Clone, Debug)]
+pub struct EmbeddedLabel<T: Scalar, const N: usize> {
+    text: String,
+    desc: Descriptor<T, N>,
+}
+
+impl<T: Scalar, const N: usize> EmbeddedLabel<T, N> {
+    pub fn new(text: impl Into<String>, desc: Descriptor<T, N>) -> Self {
+        EmbeddedLabel { text: text.into(), desc }
+    }
+
+    pub fn desc(&self) -> &Descriptor<T, N> {
+        &self.desc
+    }
+}
+
+// Written out because the derives would require `T: Eq + Hash`, which floats are not.
+impl<T: Scalar, const N: usize> PartialEq for EmbeddedLabel<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text && self.desc == other.desc
+    }
+}
+
+impl<T: Scalar, const N: usize> Eq for EmbeddedLabel<T, N> {}
+
+impl<T: Scalar, const N: usize> Hash for EmbeddedLabel<T, N> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.text.hash(state);
+        self.desc.hash(state);
+    }
+}
+
+impl<T: Scalar, const N: usize> Display for EmbeddedLabel<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl<T: Scalar, const N: usize> Label for EmbeddedLabel<T, N> {
+    fn label(&self) -> &str {
+        &self.text
+    }
+}
+
+impl<T: Scalar, const N: usize> Embedding for EmbeddedLabel<T, N> {
+    type Scalar = T;
+    fn embedding(&self) -> &[T] {
+        self.desc.as_slice()
+    }
+}
+
+/// The cosine similarity of two vectors, computed in `f64`. It is NaN when either of them is zero.
+pub fn cosine<T: Copy + Into<f64>>(x: &[T], y: &[T]) -> f64 {
+    let dot = |a: &[T], b: &[T]| a.iter().zip(b.iter()).map(|(&p, &q)| p.into() * q.into()).sum::<f64>();
+    dot(x, y) / (dot(x, x).sqrt() * dot(y, y).sqrt())
+}
+
+pub fn levenshtein(x: &str, y: &str) -> usize {
+    let y = y.chars().collect::<Vec<_>>();
+    let mut prev = (0..=y.len()).collect::<Vec<_>>();
+    let mut curr = vec![0; y.len() + 1];
+    for (i, a) in x.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, b) in y.iter().enumerate() {
+            let substitution = prev[j] + (a != *b) as usize;
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[y.len()]
+}
+
+fn tokens(s: &str) -> HashSet<String> {
+    s.split(|c: char| !c.is_alphanumeric()).filter(|t| !t.is_empty()).map(str::to_lowercase).collect()
+}
+
+/// Jaccard index of the word sets of `x` and `y`. Two labels without words are identical.
+pub fn jaccard(x: &str, y: &str) -> f64 {
+    let (x, y) = (tokens(x), tokens(y));
+    let union = x.union(&y).count();
+    if union == 0 {
+        return 1.0;
+    }
+    x.intersection(&y).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::standard::{HyperLabelGraph, LabelNode};
+    use crate::interfaces::graph::Graph;
+    use crate::interfaces::labeled::{HyperLabeled, Labeled};
+
+    #[test]
+    fn string_comparators() {
+        let (a, b) = ("Graph Theory".to_string(), "graph theory".to_string());
+        assert!(!Exact.similar(&a, &b));
+        assert!(CaseInsensitive.similar(&a, &b));
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert!(EditDistance::new(3).similar(&"kitten".to_string(), &"sitting".to_string()));
+        assert!(!EditDistance::new(2).similar(&"kitten".to_string(), &"sitting".to_string()));
+        assert_eq!(jaccard("graph-base library", "Graph library tools"), 0.5);
+        assert!(Jaccard::new(0.5).similar(&a, &"theory, graph".to_string()));
+        assert!(!Jaccard::new(0.5).similar(&a, &"theory of graphs".to_string()));
+    }
+
+    #[test]
+    fn unset_graph_compares_exactly() {
+        let mut graph = HyperLabelGraph::<String>::new();
+        graph.add_node(LabelNode::new(0, "Person".to_string()));
+        graph.add_node(LabelNode::new(1, "person".to_string()));
+        graph.add_node(LabelNode::new(2, "person".to_string()));
+        let nodes = graph.nodes().collect::<Vec<_>>();
+        assert!(graph.label_same(nodes[1], nodes[2]));
+        assert!(!graph.label_same(nodes[0], nodes[1]));
+
+        let mut graph = HyperLabelGraph::<String>::new();
+        graph.set_label_similarity(Box::new(CaseInsensitive));
+        graph.add_node(LabelNode::new(0, "Person".to_string()));
+        graph.add_node(LabelNode::new(1, "person".to_string()));
+        let nodes = graph.nodes().collect::<Vec<_>>();
+        assert!(graph.label_same(nodes[0], nodes[1]));
+    }
+
+    #[test]
+    fn cosine_threshold_on_embedded_labels() {
+        let label = |text: &str, values: [f32; 3]| EmbeddedLabel::new(text, Descriptor::new(values));
+        let mut graph = HyperLabelGraph::with_similarity(CosineThreshold::new(0.9));
+        graph.add_node(LabelNode::new(0, label("cat", [1.0, 0.0, 0.0])));
+        graph.add_node(LabelNode::new(1, label("kitten", [0.95, 0.1, 0.0])));
+        graph.add_node(LabelNode::new(2, label("car", [0.0, 1.0, 0.0])));
+        graph.add_node(LabelNode::new(3, label("void", [0.0, 0.0, 0.0])));
+        let nodes = graph.nodes().collect::<Vec<_>>();
+        assert!(graph.label_same(nodes[0], nodes[1]));
+        assert!(!graph.label_same(nodes[0], nodes[2]));
+        assert!(!graph.label_same(nodes[3], nodes[3]));
+        assert!((cosine(&[1.0f32, 1.0], &[1.0, 0.0]) - 0.5f64.sqrt()).abs() < 1e-6);
+    }
+}
@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 use std::fmt::Display;
 
-use crate::interfaces::labeled::{Label, Labeled, HyperLabeled, LabeledAdjacency};
-use crate::interfaces::graph::{SingleId, IdPair, Graph, Adjacency, AdjacencyInv, Directed};
+use crate::interfaces::labeled::{EdgeLabeled, Label, LabelIndex, LabelStatistics, Labeled, HyperLabeled, LabeledAdjacency, LabeledUnDirectedAdjacency};
+use crate::interfaces::graph::{SingleId, IdPair, Graph, Adjacency, AdjacencyInv, Directed, UnDirected, UnDirectedAdjacency};
+use crate::interfaces::similarity::LabelSimilarity;
+use crate::interfaces::typed::Type;
 use crate::interfaces::vertex::Vertex;
+use crate::impls::similarity::Exact;
 
 #[derive(Hash, Eq, PartialEq, Clone)]
 pub struct LabelNode<L: Label> {
@@ -11,6 +14,23 @@ pub struct LabelNode<L: Label> {
     label: L
 }
 
+impl<L: Label> LabelNode<L> {
+    pub fn new(id: u64, label: L) -> Self {
+        LabelNode { id, label }
+    }
+
+    pub fn label_value(&self) -> &L {
+        &self.label
+    }
+}
+
+// A node is typed when its label is.
+impl<L: Label + Type> Type for LabelNode<L> {
+    fn type_id(&self) -> usize {
+        self.label.type_id()
+    }
+}
+
 impl<L: Label> Display for LabelNode<L> 
 where L: Label {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -37,6 +57,22 @@ pub struct LabeledEdge<L: Label> {
     label: L,
 }
 
+impl<L: Label> LabeledEdge<L> {
+    pub fn new(src: u64, dst: u64, label: L) -> Self {
+        LabeledEdge { src, dst, label }
+    }
+
+    pub fn label_value(&self) -> &L {
+        &self.label
+    }
+}
+
+impl<L: Label + Type> Type for LabeledEdge<L> {
+    fn type_id(&self) -> usize {
+        self.label.type_id()
+    }
+}
+
 impl<L: Label> IdPair for LabeledEdge<L> {
     fn pair(&self) -> (usize, usize) {
         (self.src as usize, self.dst as usize)
@@ -56,18 +92,19 @@ impl<L: Label> Label for LabeledEdge<L> {
     }
 }
 
+// pub trait LabeledGraph<'a>: Graph<'a> 
+//     where <Self as Graph<'a>>::Node: Label, <Self as Graph<'a>>::Edge: Label {}
+
 /// `L1` is the label of the nodes, `L2` is the label of the edges. All these labels needs to implement the `Label` trait.
 /// 
 /// The graph is directed.
-
-// pub trait LabeledGraph<'a>: Graph<'a> 
-//     where <Self as Graph<'a>>::Node: Label, <Self as Graph<'a>>::Edge: Label {}
 pub struct SimpleLabeledGraph<L1: Label, L2: Label> {
     nodes: Vec<LabelNode<L1>>,
     edges: Vec<LabeledEdge<L2>>,
+    statistics: LabelIndex,
 }
 
-#[derive(Hash, Eq, Clone)]
+#[derive(Hash, Eq, Clone, Default)]
 pub struct SingleLabel(());
 
 impl Display for SingleLabel {
@@ -88,17 +125,19 @@ impl PartialEq for SingleLabel {
     }
 }
 
+/// A graph whose edges carry no label: every edge label compares equal.
 pub type StandardLabeledGraph = SimpleLabeledGraph<String, SingleLabel>;
 
-impl<'a> Graph<'a> for StandardLabeledGraph {
-    type Node = LabelNode<String>;
+impl<'a, L1: Label, L2: Label> Graph<'a> for SimpleLabeledGraph<L1, L2> {
+    type Node = LabelNode<L1>;
 
-    type Edge = LabeledEdge<SingleLabel>;
+    type Edge = LabeledEdge<L2>;
 
     fn new() -> Self {
-        StandardLabeledGraph {
+        SimpleLabeledGraph {
             nodes: Vec::new(),
             edges: Vec::new(),
+            statistics: LabelIndex::new(),
         }
     }
 
@@ -112,15 +151,17 @@ impl<'a> Graph<'a> for StandardLabeledGraph {
     
 
     fn add_node(&mut self, node: Self::Node) {
+        self.statistics.add_node(node.id(), node.label.label());
         self.nodes.push(node);
     }
 
     fn add_edge(&mut self, edge: Self::Edge) {
+        self.statistics.add_edge(edge.src as usize, edge.label.label(), edge.dst as usize);
         self.edges.push(edge);
     }
 }
 
-impl<'a> Labeled<'a> for StandardLabeledGraph {
+impl<'a, L1: Label, L2: Label> Labeled<'a> for SimpleLabeledGraph<L1, L2> {
     fn label_same(&self, node: &Self::Node, label: &Self::Node) -> bool {
         node.label == label.label
     }
@@ -130,52 +171,85 @@ impl<'a> Labeled<'a> for StandardLabeledGraph {
     }
 
     fn get_edges_pair_label(&'a self) -> impl Iterator<Item = (&'a Self::Node, &'a Self::Node, &'a impl Label)> {
-        let id_map: HashMap<_, _, std::collections::hash_map::RandomState> = HashMap::from_iter(self.nodes.iter().map(|node| (node.id, node)));
-        self.edges.iter().map(move |edge| (id_map.get(&edge.src).unwrap().clone(), id_map.get(&edge.dst).unwrap().clone(), &edge.label)).collect::<Vec<_>>().into_iter()
+        let id_map: HashMap<_, _> = HashMap::from_iter(self.nodes.iter().map(|node| (node.id, node)));
+        self.edges.iter().map(move |edge| (id_map[&edge.src], id_map[&edge.dst], &edge.label)).collect::<Vec<_>>().into_iter()
     }
 
-    fn edge_label_same(&self, _: &Self::Edge, _: &Self::Edge) -> bool {
-        true
+    fn edge_label_same(&self, edge1: &Self::Edge, edge2: &Self::Edge) -> bool {
+        edge1.label == edge2.label
     }
 
-    fn edge_node_label_same(&self, _: &Self::Node, _: &Self::Edge, _: &Self::Node, _: &Self::Node, _: &Self::Edge, _: &Self::Node) -> bool {
-        true
+    fn edge_node_label_same(&self, src1: &Self::Node, edge1: &Self::Edge, dst1: &Self::Node, src2: &Self::Node, edge2: &Self::Edge, dst2: &Self::Node) -> bool {
+        self.label_same(src1, src2) && self.edge_label_same(edge1, edge2) && self.label_same(dst1, dst2)
     }
 }
 
-impl Directed for StandardLabeledGraph {}
+impl<'a, L1: Label, L2: Label> EdgeLabeled<'a> for SimpleLabeledGraph<L1, L2> {
+    fn get_edge_label(&'a self, edge: &'a Self::Edge) -> &'a impl Label {
+        &edge.label
+    }
+}
 
-impl Adjacency<'_> for StandardLabeledGraph {}
+impl<'a, L1: Label, L2: Label> LabelStatistics<'a> for SimpleLabeledGraph<L1, L2> {
+    fn label_index(&self) -> &LabelIndex {
+        &self.statistics
+    }
+
+    fn node_at(&'a self, position: usize) -> &'a Self::Node {
+        &self.nodes[position]
+    }
+
+    fn edge_at(&'a self, position: usize) -> &'a Self::Edge {
+        &self.edges[position]
+    }
+}
+
+impl<L1: Label, L2: Label> Directed for SimpleLabeledGraph<L1, L2> {}
+
+impl<L1: Label, L2: Label> Adjacency<'_> for SimpleLabeledGraph<L1, L2> {}
     
-impl AdjacencyInv<'_> for StandardLabeledGraph {}
+impl<L1: Label, L2: Label> AdjacencyInv<'_> for SimpleLabeledGraph<L1, L2> {}
 
-impl LabeledAdjacency<'_> for StandardLabeledGraph {}
+impl<L1: Label, L2: Label> LabeledAdjacency<'_> for SimpleLabeledGraph<L1, L2> {}
 
-impl StandardLabeledGraph {
-    pub fn new() -> Self {
-        return StandardLabeledGraph {
+impl<L1: Label, L2: Label> Default for SimpleLabeledGraph<L1, L2> {
+    fn default() -> Self {
+        SimpleLabeledGraph {
             nodes: Vec::new(),
             edges: Vec::new(),
+            statistics: LabelIndex::new(),
         }
     }
+}
 
-    pub fn add_node(&mut self, id: u64, label: String) {
-        self.nodes.push(LabelNode {
+impl<L1: Label, L2: Label> SimpleLabeledGraph<L1, L2> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_node(&mut self, id: u64, label: L1) {
+        Graph::add_node(self, LabelNode {
             id,
             label
         });
     }
 
-    pub fn add_edge(&mut self, src: u64, dst: u64) {
-        self.edges.push(LabeledEdge {
+    pub fn add_labeled_edge(&mut self, src: u64, dst: u64, label: L2) {
+        Graph::add_edge(self, LabeledEdge {
             src,
             dst,
-            label: SingleLabel(())
+            label
         });
     }
 }
 
-impl Display for StandardLabeledGraph {
+impl StandardLabeledGraph {
+    pub fn add_edge(&mut self, src: u64, dst: u64) {
+        self.add_labeled_edge(src, dst, SingleLabel(()));
+    }
+}
+
+impl<L1: Label, L2: Label> Display for SimpleLabeledGraph<L1, L2> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut s = String::new();
         s.push_str("nodes: ");
@@ -184,17 +258,23 @@ impl Display for StandardLabeledGraph {
         }
         s.push_str("\nedges: ");
         for edge in self.edges.iter() {
-            s.push_str(format!("{} -> {}, ", edge.src, edge.dst).as_str());
+            if edge.label.label().is_empty() {
+                s.push_str(format!("{} -> {}, ", edge.src, edge.dst).as_str());
+            } else {
+                s.push_str(format!("{} -[{}]-> {}, ", edge.src, edge.label, edge.dst).as_str());
+            }
         }
         write!(f, "{}", s)
     }
 }
 impl<L: Label> Vertex for LabelNode<L> {}
 
+/// Node labels are compared with a pluggable `LabelSimilarity` (or a plain compare function), `Exact` unless one is set.
 pub struct HyperLabelGraph<L: Label> {
     nodes: Vec<LabelNode<L>>,
     edges: Vec<LabeledEdge<SingleLabel>>,
-    same_label_fn: Option<Box<dyn Fn(&L, &L) -> bool>>,
+    statistics: LabelIndex,
+    similarity: Box<dyn LabelSimilarity<L>>,
 }
 
 impl<'a, L: Label> Graph<'a> for HyperLabelGraph<L> {
@@ -202,11 +282,7 @@ impl<'a, L: Label> Graph<'a> for HyperLabelGraph<L> {
     type Edge = LabeledEdge<SingleLabel>;
 
     fn new() -> Self {
-        HyperLabelGraph {
-            nodes: Vec::new(),
-            edges: Vec::new(),
-            same_label_fn: None,
-        }
+        Self::default()
     }
 
     fn nodes(&'a self) -> impl Iterator<Item = &'a Self::Node> {
@@ -218,17 +294,19 @@ impl<'a, L: Label> Graph<'a> for HyperLabelGraph<L> {
     }
 
     fn add_node(&mut self, node: Self::Node) {
+        self.statistics.add_node(node.id(), node.label.label());
         self.nodes.push(node);
     }
 
     fn add_edge(&mut self, edge: Self::Edge) {
+        self.statistics.add_edge(edge.src as usize, edge.label.label(), edge.dst as usize);
         self.edges.push(edge);
     }
 }
 
 impl<'a, L: Label> Labeled<'a> for HyperLabelGraph<L> {
     fn label_same(&self, node: &Self::Node, label: &Self::Node) -> bool {
-        (self.same_label_fn.as_ref().expect("hyper compare function is not set"))(&node.label, &label.label)
+        self.similarity.similar(&node.label, &label.label)
     }
 
     fn get_label(&'a self, node: &'a Self::Node) -> &'a impl Label {
@@ -249,10 +327,34 @@ impl<'a, L: Label> Labeled<'a> for HyperLabelGraph<L> {
     }
 }
 
-impl<L: Label> HyperLabeled<'_> for HyperLabelGraph<L> {
+impl<'a, L: Label> EdgeLabeled<'a> for HyperLabelGraph<L> {
+    fn get_edge_label(&'a self, edge: &'a Self::Edge) -> &'a impl Label {
+        &edge.label
+    }
+}
+
+impl<'a, L: Label> LabelStatistics<'a> for HyperLabelGraph<L> {
+    fn label_index(&self) -> &LabelIndex {
+        &self.statistics
+    }
+
+    fn node_at(&'a self, position: usize) -> &'a Self::Node {
+        &self.nodes[position]
+    }
+
+    fn edge_at(&'a self, position: usize) -> &'a Self::Edge {
+        &self.edges[position]
+    }
+}
+
+impl<L: Label + 'static> HyperLabeled<'_> for HyperLabelGraph<L> {
     type L = L;
     fn set_same_label_fn(&mut self, f: Box<dyn Fn(&Self::L, &Self::L) -> bool>) {
-        self.same_label_fn = Some(f);
+        self.similarity = Box::new(f);
+    }
+
+    fn set_label_similarity(&mut self, similarity: Box<dyn LabelSimilarity<Self::L>>) {
+        self.similarity = similarity;
     }
 }
 
@@ -262,13 +364,262 @@ impl Adjacency<'_> for HyperLabelGraph<SingleLabel> {}
     
 impl AdjacencyInv<'_> for HyperLabelGraph<SingleLabel> {}
 
+impl<L: Label> Default for HyperLabelGraph<L> {
+    fn default() -> Self {
+        HyperLabelGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            statistics: LabelIndex::new(),
+            similarity: Box::new(Exact),
+        }
+    }
+}
+
 impl<L: Label> HyperLabelGraph<L> {
     pub fn new() -> Self {
-        return HyperLabelGraph {
+        Self::default()
+    }
+
+    pub fn with_similarity(similarity: impl LabelSimilarity<L> + 'static) -> Self {
+        HyperLabelGraph {
+            similarity: Box::new(similarity),
+            ..Self::default()
+        }
+    }
+}
+
+/// `L1` is the label of the nodes, `L2` is the label of the edges.
+///
+/// The graph is undirected: an edge `src -> dst` also connects `dst` to `src`, so each edge is stored once.
+pub struct UnDirectedLabeledGraph<L1: Label, L2: Label> {
+    nodes: Vec<LabelNode<L1>>,
+    edges: Vec<LabeledEdge<L2>>,
+    statistics: LabelIndex,
+}
+
+pub type StandardUnDirectedGraph = UnDirectedLabeledGraph<String, SingleLabel>;
+
+impl<'a, L1: Label, L2: Label> Graph<'a> for UnDirectedLabeledGraph<L1, L2> {
+    type Node = LabelNode<L1>;
+    type Edge = LabeledEdge<L2>;
+
+    fn new() -> Self {
+        UnDirectedLabeledGraph {
             nodes: Vec::new(),
             edges: Vec::new(),
-            same_label_fn: None,
+            statistics: LabelIndex::new(),
         }
     }
-    
+
+    fn nodes(&'a self) -> impl Iterator<Item = &'a Self::Node> {
+        self.nodes.iter()
+    }
+
+    fn edges(&'a self) -> impl Iterator<Item = &'a Self::Edge> {
+        self.edges.iter()
+    }
+
+    fn add_node(&mut self, node: Self::Node) {
+        self.statistics.add_node(node.id(), node.label.label());
+        self.nodes.push(node);
+    }
+
+    fn add_edge(&mut self, edge: Self::Edge) {
+        self.statistics.add_edge(edge.src as usize, edge.label.label(), edge.dst as usize);
+        self.edges.push(edge);
+    }
+}
+
+impl<'a, L1: Label, L2: Label> Labeled<'a> for UnDirectedLabeledGraph<L1, L2> {
+    fn label_same(&self, node: &Self::Node, label: &Self::Node) -> bool {
+        node.label == label.label
+    }
+
+    fn get_label(&'a self, node: &'a Self::Node) -> &'a impl Label {
+        &node.label
+    }
+
+    fn get_edges_pair_label(&'a self) -> impl Iterator<Item = (&'a Self::Node, &'a Self::Node, &'a impl Label)> {
+        let id_map: HashMap<_, _> = HashMap::from_iter(self.nodes.iter().map(|node| (node.id, node)));
+        self.edges.iter().map(move |edge| (id_map[&edge.src], id_map[&edge.dst], &edge.label)).collect::<Vec<_>>().into_iter()
+    }
+
+    fn edge_label_same(&self, edge1: &Self::Edge, edge2: &Self::Edge) -> bool {
+        edge1.label == edge2.label
+    }
+
+    // The edges match in either orientation.
+    fn edge_node_label_same(&self, src1: &Self::Node, edge1: &Self::Edge, dst1: &Self::Node, src2: &Self::Node, edge2: &Self::Edge, dst2: &Self::Node) -> bool {
+        self.edge_label_same(edge1, edge2)
+            && ((self.label_same(src1, src2) && self.label_same(dst1, dst2)) || (self.label_same(src1, dst2) && self.label_same(dst1, src2)))
+    }
+}
+
+impl<'a, L1: Label, L2: Label> EdgeLabeled<'a> for UnDirectedLabeledGraph<L1, L2> {
+    fn get_edge_label(&'a self, edge: &'a Self::Edge) -> &'a impl Label {
+        &edge.label
+    }
+}
+
+impl<'a, L1: Label, L2: Label> LabelStatistics<'a> for UnDirectedLabeledGraph<L1, L2> {
+    fn label_index(&self) -> &LabelIndex {
+        &self.statistics
+    }
+
+    fn node_at(&'a self, position: usize) -> &'a Self::Node {
+        &self.nodes[position]
+    }
+
+    fn edge_at(&'a self, position: usize) -> &'a Self::Edge {
+        &self.edges[position]
+    }
+}
+
+impl<L1: Label, L2: Label> UnDirected for UnDirectedLabeledGraph<L1, L2> {}
+
+impl<L1: Label, L2: Label> UnDirectedAdjacency<'_> for UnDirectedLabeledGraph<L1, L2> {}
+
+impl<L1: Label, L2: Label> LabeledUnDirectedAdjacency<'_> for UnDirectedLabeledGraph<L1, L2> {}
+
+impl<L1: Label, L2: Label> Default for UnDirectedLabeledGraph<L1, L2> {
+    fn default() -> Self {
+        UnDirectedLabeledGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            statistics: LabelIndex::new(),
+        }
+    }
+}
+
+impl<L1: Label, L2: Label> UnDirectedLabeledGraph<L1, L2> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_node(&mut self, id: u64, label: L1) {
+        Graph::add_node(self, LabelNode::new(id, label));
+    }
+
+    pub fn add_edge(&mut self, src: u64, dst: u64, label: L2) {
+        Graph::add_edge(self, LabeledEdge::new(src, dst, label));
+    }
+}
+
+impl<L1: Label, L2: Label> Display for UnDirectedLabeledGraph<L1, L2> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = String::new();
+        s.push_str("nodes: ");
+        for node in self.nodes.iter() {
+            s.push_str(format!("{}, ", node).as_str());
+        }
+        s.push_str("\nedges: ");
+        for edge in self.edges.iter() {
+            s.push_str(format!("{} -- {}, ", edge.src, edge.dst).as_str());
+        }
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edge_labels_take_part_in_matching() {
+        let mut graph = SimpleLabeledGraph::<String, String>::new();
+        graph.add_node(0, "person".to_string());
+        graph.add_node(1, "person".to_string());
+        graph.add_node(2, "company".to_string());
+        graph.add_labeled_edge(0, 1, "knows".to_string());
+        graph.add_labeled_edge(0, 2, "works_at".to_string());
+        graph.add_labeled_edge(1, 2, "works_at".to_string());
+
+        let edges = graph.get_edges_pair_with_edge().collect::<Vec<_>>();
+        let (s0, e0, d0) = edges[0];
+        let (s1, e1, d1) = edges[1];
+        let (s2, e2, d2) = edges[2];
+        assert!(!graph.edge_label_same(e0, e1));
+        assert!(graph.edge_node_label_same(s1, e1, d1, s2, e2, d2));
+        assert!(!graph.edge_node_label_same(s0, e0, d0, s1, e1, d1));
+
+        let adj = graph.get_labeled_adj();
+        let labels = graph.get_labeled_post(&adj, s0).map(|(_, edge)| edge.label().to_string()).collect::<Vec<_>>();
+        assert_eq!(labels, vec!["knows", "works_at"]);
+    }
+
+    #[test]
+    fn undirected_edges_are_seen_from_both_ends() {
+        let mut graph = UnDirectedLabeledGraph::<String, String>::new();
+        graph.add_node(0, "person".to_string());
+        graph.add_node(1, "person".to_string());
+        graph.add_node(2, "company".to_string());
+        graph.add_edge(0, 2, "works_at".to_string());
+        graph.add_edge(1, 1, "self".to_string());
+        graph.add_edge(1, 0, "knows".to_string());
+
+        let node = |id: usize| graph.nodes().find(|node| node.id() == id).unwrap();
+        let ids = |nodes: Vec<&LabelNode<String>>| nodes.into_iter().map(|node| node.id()).collect::<Vec<_>>();
+        let adj = graph.get_neighbors();
+        assert_eq!(ids(graph.neighbors(&adj, node(0)).collect()), vec![2, 1]);
+        assert_eq!(ids(graph.neighbors(&adj, node(2)).collect()), vec![0]);
+        // A self-loop is one neighbour entry, not two.
+        assert_eq!(ids(graph.neighbors(&adj, node(1)).collect()), vec![1, 0]);
+        assert_eq!(graph.degree(&adj, node(1)), 2);
+
+        let labeled = graph.get_labeled_neighbors();
+        let labels = graph.get_labeled_neighbor(&labeled, node(2)).map(|(other, edge)| (other.id(), edge.label().to_string())).collect::<Vec<_>>();
+        assert_eq!(labels, vec![(0, "works_at".to_string())]);
+
+        // `person -works_at- company` matches the same edge written the other way round.
+        let mut other = UnDirectedLabeledGraph::<String, String>::new();
+        other.add_node(5, "company".to_string());
+        other.add_node(6, "person".to_string());
+        other.add_edge(5, 6, "works_at".to_string());
+        let (s1, e1, d1) = graph.get_edges_pair_with_edge().next().unwrap();
+        let (s2, e2, d2) = other.get_edges_pair_with_edge().next().unwrap();
+        assert!(graph.edge_node_label_same(s1, e1, d1, s2, e2, d2));
+        assert!(graph.edge_node_label_same(s1, e1, d1, d2, e2, s2));
+        let (s3, e3, d3) = graph.get_edges_pair_with_edge().nth(2).unwrap();
+        assert!(!graph.edge_node_label_same(s1, e1, d1, s3, e3, d3));
+    }
+
+    #[test]
+    fn label_index_is_maintained() {
+        let mut graph = SimpleLabeledGraph::<String, String>::new();
+        graph.add_node(0, "person".to_string());
+        graph.add_node(1, "person".to_string());
+        graph.add_node(2, "company".to_string());
+        graph.add_labeled_edge(0, 1, "knows".to_string());
+        graph.add_labeled_edge(0, 2, "works_at".to_string());
+        // An edge to a node that is not there yet is counted once the node is added.
+        graph.add_labeled_edge(1, 3, "works_at".to_string());
+
+        let index = graph.label_index();
+        assert_eq!((index.node_count(), index.edge_count()), (3, 3));
+        assert_eq!(index.node_label_frequency("person"), 2);
+        assert_eq!(index.edge_label_frequency("works_at"), 2);
+        assert_eq!(index.pair_count("person", "company"), 1);
+        assert_eq!(index.rarest(["person", "company"]), Some("company"));
+
+        graph.add_node(3, "company".to_string());
+        graph.add_labeled_edge(2, 2, "owns".to_string());
+        let index = graph.label_index();
+        let mut frequencies = index.node_label_frequencies().collect::<Vec<_>>();
+        frequencies.sort();
+        assert_eq!(frequencies, vec![("company", 2), ("person", 2)]);
+        assert_eq!(index.pair_count("person", "company"), 2);
+        assert_eq!(index.pair_count("company", "person"), 0);
+        assert_eq!(index.triple_count("person", "works_at", "company"), 2);
+        assert_eq!(index.triple_count("person", "knows", "person"), 1);
+        assert_eq!(index.triple_count("company", "owns", "company"), 1);
+        assert_eq!(index.triple_count("person", "owns", "company"), 0);
+        assert_eq!(index.pair_counts().map(|(_, count)| count).sum::<usize>(), 4);
+        assert_eq!(index.selectivity("company"), 0.5);
+        assert_eq!(index.pair_selectivity("person", "company"), 0.5);
+
+        assert_eq!(graph.nodes_labeled("company").map(|n| n.id()).collect::<Vec<_>>(), vec![2, 3]);
+        let pairs = graph.edges_labeled("works_at").map(|e| e.pair()).collect::<Vec<_>>();
+        assert_eq!(pairs, vec![(0, 2), (1, 3)]);
+        assert_eq!(graph.edges_labeled("ghost").count(), 0);
+    }
 }
@@ -0,0 +1,43 @@
+use crate::impls::hypergraph::{Descriptor, FeatureNode, NodeType, Scalar};
+use crate::interfaces::graph::SingleId;
+
+use super::{top_k, Entry, Neighbor, VectorIndex};
+
+/// Exact search by scanning every descriptor.
+pub struct BruteForceIndex<T: Scalar = f64, const N: usize = 16> {
+    entries: Vec<Entry<T, N>>,
+}
+
+impl<T: Scalar, const N: usize> Default for BruteForceIndex<T, N> {
+    fn default() -> Self {
+        BruteForceIndex { entries: Vec::new() }
+    }
+}
+
+impl<T: Scalar, const N: usize> BruteForceIndex<T, N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_nodes<'n>(nodes: impl IntoIterator<Item = &'n FeatureNode<T, N>>) -> Self {
+        let mut index = Self::new();
+        for node in nodes {
+            index.insert(node);
+        }
+        index
+    }
+
+    pub fn insert(&mut self, node: &FeatureNode<T, N>) {
+        self.entries.push(Entry::new(node.id(), node.node_type().clone(), node.desc().clone()));
+    }
+}
+
+impl<T: Scalar, const N: usize> VectorIndex<T, N> for BruteForceIndex<T, N> {
+    fn search(&self, query: &Descriptor<T, N>, k: usize, filter: Option<&NodeType>) -> Vec<Neighbor> {
+        top_k(self.entries.iter(), query, k, filter)
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
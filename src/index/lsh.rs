@@ -0,0 +1,118 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::Rng;
+
+use crate::impls::hypergraph::{Descriptor, FeatureNode, NodeType, Scalar};
+use crate::interfaces::graph::SingleId;
+
+use super::{dot, top_k, Entry, Neighbor, VectorIndex};
+
+/// Approximate search with random-hyperplane (SimHash) locality-sensitive hashing.
+///
+/// Each of the `tables` hash tables keys a descriptor by the signs of its projections on `bits` random hyperplanes.
+/// A query probes its own bucket and every bucket one bit away in each table, then ranks the candidates exactly.
+/// More tables raise recall, more bits make buckets smaller.
+pub struct LshIndex<T: Scalar = f64, const N: usize = 16> {
+    bits: usize,
+    planes: Vec<Vec<[f64; N]>>,
+    tables: Vec<HashMap<u64, Vec<usize>>>,
+    entries: Vec<Entry<T, N>>,
+}
+
+impl<T: Scalar, const N: usize> LshIndex<T, N> {
+    pub fn new(tables: usize, bits: usize, rng: &mut impl Rng) -> Self {
+        assert!((1..=64).contains(&bits), "an LSH signature has between 1 and 64 bits");
+        let planes = (0..tables).map(|_| {
+            (0..bits).map(|_| std::array::from_fn(|_| gaussian(rng))).collect()
+        }).collect();
+        LshIndex { bits, planes, tables: vec![HashMap::new(); tables], entries: Vec::new() }
+    }
+
+    pub fn from_nodes<'n>(nodes: impl IntoIterator<Item = &'n FeatureNode<T, N>>, tables: usize, bits: usize, rng: &mut impl Rng) -> Self {
+        let mut index = Self::new(tables, bits, rng);
+        for node in nodes {
+            index.insert(node);
+        }
+        index
+    }
+
+    fn signature(&self, table: usize, desc: &Descriptor<T, N>) -> u64 {
+        self.planes[table].iter().enumerate().fold(0, |sig, (bit, plane)| {
+            if dot(plane, desc.as_slice()) >= 0.0 { sig | 1 << bit } else { sig }
+        })
+    }
+
+    pub fn insert(&mut self, node: &FeatureNode<T, N>) {
+        let position = self.entries.len();
+        for table in 0..self.tables.len() {
+            let sig = self.signature(table, node.desc());
+            self.tables[table].entry(sig).or_default().push(position);
+        }
+        self.entries.push(Entry::new(node.id(), node.node_type().clone(), node.desc().clone()));
+    }
+
+    fn candidates(&self, query: &Descriptor<T, N>) -> HashSet<usize> {
+        let mut candidates = HashSet::new();
+        for (table, buckets) in self.tables.iter().enumerate() {
+            let sig = self.signature(table, query);
+            let probes = std::iter::once(sig).chain((0..self.bits).map(|bit| sig ^ (1 << bit)));
+            for probe in probes {
+                if let Some(bucket) = buckets.get(&probe) {
+                    candidates.extend(bucket.iter().copied());
+                }
+            }
+        }
+        candidates
+    }
+}
+
+// Box-Muller, so hyperplane normals are uniformly distributed in direction.
+fn gaussian(rng: &mut impl Rng) -> f64 {
+    let u: f64 = 1.0 - rng.random::<f64>();
+    let v: f64 = rng.random();
+    (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+}
+
+impl<T: Scalar, const N: usize> VectorIndex<T, N> for LshIndex<T, N> {
+    fn search(&self, query: &Descriptor<T, N>, k: usize, filter: Option<&NodeType>) -> Vec<Neighbor> {
+        let candidates = self.candidates(query);
+        top_k(candidates.into_iter().map(|i| &self.entries[i]), query, k, filter)
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::hypergraph::{Desc, Node};
+    use crate::index::brute::BruteForceIndex;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn lsh_finds_the_exact_match() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let nodes = (0..500).map(|id| {
+            let mut values = [0.0; 16];
+            for v in values.iter_mut() {
+                *v = rng.random_range(-1.0..1.0);
+            }
+            Node::new(id, NodeType::new(id % 3), Desc::new(values))
+        }).collect::<Vec<_>>();
+
+        let brute = BruteForceIndex::from_nodes(&nodes);
+        let lsh = LshIndex::from_nodes(&nodes, 8, 10, &mut rng);
+        let query = nodes[42].desc();
+
+        let exact = brute.search(query, 5, None);
+        assert_eq!(exact[0].id, 42);
+        assert!(exact.windows(2).all(|w| w[0].score >= w[1].score));
+        assert_eq!(lsh.search(query, 1, None)[0].id, 42);
+
+        let typed = brute.search(query, 5, Some(&NodeType::new(1)));
+        assert!(typed.iter().all(|n| n.id % 3 == 1));
+    }
+}
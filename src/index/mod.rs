@@ -0,0 +1,93 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::impls::hypergraph::{Descriptor, NodeType, Scalar};
+
+pub mod brute;
+pub mod lsh;
+
+/// A node found by a vector search, with the cosine similarity between its descriptor and the query.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Neighbor {
+    pub id: usize,
+    pub score: f64,
+}
+
+/// Nearest-neighbour search over node descriptors by cosine similarity.
+pub trait VectorIndex<T: Scalar = f64, const N: usize = 16> {
+    /// The `k` most similar nodes to `query`, best first. With `filter`, only nodes of that type are returned.
+    fn search(&self, query: &Descriptor<T, N>, k: usize, filter: Option<&NodeType>) -> Vec<Neighbor>;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+pub(crate) struct Entry<T: Scalar, const N: usize> {
+    pub id: usize,
+    pub node_type: NodeType,
+    pub desc: Descriptor<T, N>,
+    pub norm: f64,
+}
+
+impl<T: Scalar, const N: usize> Entry<T, N> {
+    pub fn new(id: usize, node_type: NodeType, desc: Descriptor<T, N>) -> Self {
+        let norm = dot(desc.as_slice(), desc.as_slice()).sqrt();
+        Entry { id, node_type, desc, norm }
+    }
+
+    // Cosine similarity, or `None` when either vector is zero.
+    pub fn cosine(&self, query: &Descriptor<T, N>, query_norm: f64) -> Option<f64> {
+        let denom = self.norm * query_norm;
+        (denom > 0.0).then(|| dot(self.desc.as_slice(), query.as_slice()) / denom)
+    }
+}
+
+// Accumulated in `f64` whatever the element type.
+pub(crate) fn dot<T: Scalar, U: Scalar>(x: &[T], y: &[U]) -> f64 {
+    x.iter().zip(y.iter()).map(|(a, b)| a.to_f64() * b.to_f64()).sum()
+}
+
+// Min-heap entry so the heap root is the worst of the current top k.
+struct Scored(Neighbor);
+
+impl PartialEq for Scored {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Scored {}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.score.total_cmp(&self.0.score).then(self.0.id.cmp(&other.0.id))
+    }
+}
+
+/// Keep the `k` best-scoring candidates, best first.
+pub(crate) fn top_k<'e, T: Scalar, const N: usize>(entries: impl Iterator<Item = &'e Entry<T, N>>, query: &Descriptor<T, N>, k: usize, filter: Option<&NodeType>) -> Vec<Neighbor> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let query_norm = dot(query.as_slice(), query.as_slice()).sqrt();
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for entry in entries {
+        if filter.is_some_and(|t| *t != entry.node_type) {
+            continue;
+        }
+        if let Some(score) = entry.cosine(query, query_norm) {
+            heap.push(Scored(Neighbor { id: entry.id, score }));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+    }
+    heap.into_sorted_vec().into_iter().map(|s| s.0).collect()
+}
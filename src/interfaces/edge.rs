@@ -1,29 +1,179 @@
 use std::fmt::Display;
 use std::hash::Hash;
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 
 use super::hypergraph::IdVector;
 
+/// How the members of a hyperedge are compared.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum HyperedgeKind {
+    /// Order and repetitions are ignored.
+    Set,
+    /// Repetitions count, order is ignored.
+    Multiset,
+    /// Members form a tuple: order and repetitions both count.
+    Ordered,
+}
+
+/// The comparisons follow the kind of `self`. An ordered hyperedge is a subset of another when its members appear in the
+/// other in the same order, not necessarily next to each other.
+///
+/// Set algebra runs by merging sorted member slices. Sets use their distinct members; multisets and ordered hyperedges keep
+/// repetitions, so union, intersection and difference take the maximum, minimum and difference of multiplicities.
+/// Results are in ascending order. Implementations that keep their members sorted should override `sorted_ids` and
+/// `sorted_members` to avoid sorting on every call.
 pub trait Hyperedge: Eq + Hash + Clone + IdVector {
     fn id_set(&self) -> HashSet<usize>;
+    fn kind(&self) -> HyperedgeKind {
+        HyperedgeKind::Set
+    }
+    /// The distinct members, ascending.
+    fn sorted_ids(&self) -> Cow<'_, [usize]> {
+        let mut ids = self.id();
+        ids.sort_unstable();
+        ids.dedup();
+        Cow::Owned(ids)
+    }
+    /// Every member with its repetitions, ascending.
+    fn sorted_members(&self) -> Cow<'_, [usize]> {
+        let mut ids = self.id();
+        ids.sort_unstable();
+        Cow::Owned(ids)
+    }
+    fn multiplicities(&self) -> HashMap<usize, usize> {
+        let mut counts = HashMap::new();
+        for id in self.id() {
+            *counts.entry(id).or_insert(0) += 1;
+        }
+        counts
+    }
+    /// The members set algebra works on: distinct members for sets, all members otherwise.
+    fn algebra_members(&self) -> Cow<'_, [usize]> {
+        match self.kind() {
+            HyperedgeKind::Set => self.sorted_ids(),
+            HyperedgeKind::Multiset | HyperedgeKind::Ordered => self.sorted_members(),
+        }
+    }
     fn is_subset(&self, other: &Self) -> bool {
-        self.id_set().is_subset(&other.id_set())
+        match self.kind() {
+            HyperedgeKind::Set | HyperedgeKind::Multiset => sorted_is_subset(&self.algebra_members(), &other.algebra_members()),
+            HyperedgeKind::Ordered => {
+                let theirs = other.id();
+                let mut rest = theirs.iter();
+                self.id().iter().all(|id| rest.any(|other| other == id))
+            }
+        }
     }
     fn is_equal(&self, other: &Self) -> bool {
-        self.id_set().is_subset(&other.id_set()) && other.id_set().is_subset(&self.id_set())
+        match self.kind() {
+            HyperedgeKind::Set | HyperedgeKind::Multiset => self.algebra_members() == other.algebra_members(),
+            HyperedgeKind::Ordered => self.id() == other.id(),
+        }
     }
     fn has_intersection(&self, other: &Self) -> bool {
         !self.is_disjoint(other)
     }
+    // Sharing a member is the same under every kind.
     fn is_disjoint(&self, other: &Self) -> bool {
-        self.id_set().is_disjoint(&other.id_set())
+        sorted_intersection_size(&self.sorted_ids(), &other.sorted_ids()) == 0
     }
     fn is_empty(&self) -> bool {
-        self.id_set().is_empty()
+        self.id().is_empty()
     }
     fn is_singleton(&self) -> bool {
-        self.id_set().len() == 1
+        match self.kind() {
+            HyperedgeKind::Set => self.sorted_ids().len() == 1,
+            HyperedgeKind::Multiset | HyperedgeKind::Ordered => self.id().len() == 1,
+        }
+    }
+    fn union(&self, other: &Self) -> Vec<usize> {
+        sorted_union(&self.algebra_members(), &other.algebra_members())
+    }
+    fn intersection(&self, other: &Self) -> Vec<usize> {
+        sorted_intersection(&self.algebra_members(), &other.algebra_members())
+    }
+    fn difference(&self, other: &Self) -> Vec<usize> {
+        sorted_difference(&self.algebra_members(), &other.algebra_members())
+    }
+    fn intersection_size(&self, other: &Self) -> usize {
+        sorted_intersection_size(&self.algebra_members(), &other.algebra_members())
+    }
+}
+
+// Merges over ascending slices. Repeated ids are matched one to one, which gives multiset semantics and reduces to set
+// semantics when the slices have no repetitions.
+
+pub fn sorted_is_subset(x: &[usize], y: &[usize]) -> bool {
+    let mut j = 0;
+    for &a in x {
+        while j < y.len() && y[j] < a {
+            j += 1;
+        }
+        if j == y.len() || y[j] != a {
+            return false;
+        }
+        j += 1;
     }
+    true
+}
+
+pub fn sorted_union(x: &[usize], y: &[usize]) -> Vec<usize> {
+    let mut result = Vec::with_capacity(x.len() + y.len());
+    let (mut i, mut j) = (0, 0);
+    while i < x.len() && j < y.len() {
+        match x[i].cmp(&y[j]) {
+            Ordering::Less => { result.push(x[i]); i += 1; }
+            Ordering::Greater => { result.push(y[j]); j += 1; }
+            Ordering::Equal => { result.push(x[i]); i += 1; j += 1; }
+        }
+    }
+    result.extend_from_slice(&x[i..]);
+    result.extend_from_slice(&y[j..]);
+    result
+}
+
+pub fn sorted_intersection(x: &[usize], y: &[usize]) -> Vec<usize> {
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < x.len() && j < y.len() {
+        match x[i].cmp(&y[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => { result.push(x[i]); i += 1; j += 1; }
+        }
+    }
+    result
+}
+
+pub fn sorted_difference(x: &[usize], y: &[usize]) -> Vec<usize> {
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < x.len() {
+        if j == y.len() || x[i] < y[j] {
+            result.push(x[i]);
+            i += 1;
+        } else if x[i] > y[j] {
+            j += 1;
+        } else {
+            i += 1;
+            j += 1;
+        }
+    }
+    result
+}
+
+pub fn sorted_intersection_size(x: &[usize], y: &[usize]) -> usize {
+    let (mut i, mut j, mut count) = (0, 0, 0);
+    while i < x.len() && j < y.len() {
+        match x[i].cmp(&y[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => { count += 1; i += 1; j += 1; }
+        }
+    }
+    count
 }
 
 pub trait DirectedHyperedge: Hyperedge {
@@ -41,6 +191,11 @@ where T: DirectedHyperedge {
     }
 }
 
+/// Values with a weight, such as weighted hyperedges. Hyperedge labels and types use `Label` and `Type`.
+pub trait Weighted {
+    fn weight(&self) -> f64;
+}
+
 // A hyperedge is only a set of nodes.
 pub trait NodeSet: Hyperedge {
     fn from_nodes(nodes: Vec<usize>) -> Self;
@@ -34,6 +34,13 @@ pub trait UnDirected {}
 
 pub struct AdjacencyList<'a, T: Graph<'a>>(HashMap<&'a T::Node, Vec<&'a T::Node>>);
 
+// An empty table, for graphs that answer `get_post`/`get_pre` without one.
+impl<'a, T: Graph<'a>> Default for AdjacencyList<'a, T> {
+    fn default() -> Self {
+        AdjacencyList(HashMap::new())
+    }
+}
+
 impl<'a, T> Display for AdjacencyList<'a, T> 
 where T: Graph<'a> {    
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -83,4 +90,27 @@ pub trait AdjacencyInv<'a>: Graph<'a> + Directed + Sized {
     fn get_pre(&'a self, adj_inv: &AdjacencyList<'a, Self>, node: &Self::Node) -> impl Iterator<Item = &'a Self::Node> {
         adj_inv.0.get(node).expect(format!("No node in adjacency table named {} \n adj is: {}", node, adj_inv).as_str()).iter().copied()
     }
-}
\ No newline at end of file
+}
+
+// Each edge of an undirected graph is seen from both of its ends.
+pub trait UnDirectedAdjacency<'a>: Graph<'a> + UnDirected + Sized {
+    fn get_neighbors(&'a self) -> AdjacencyList<'a, Self> {
+        let mut adj = HashMap::new();
+        for node in self.nodes() {
+            adj.insert(node, Vec::new());
+        }
+        for (u, v) in self.get_edges_pair() {
+            adj.get_mut(u).unwrap().push(v);
+            if u != v {
+                adj.get_mut(v).unwrap().push(u);
+            }
+        }
+        AdjacencyList(adj)
+    }
+    fn neighbors(&'a self, adj: &AdjacencyList<'a, Self>, node: &Self::Node) -> impl Iterator<Item = &'a Self::Node> {
+        adj.0.get(node).unwrap_or_else(|| panic!("No node in adjacency table named {} \n adj is: {}", node, adj)).iter().copied()
+    }
+    fn degree(&'a self, adj: &AdjacencyList<'a, Self>, node: &Self::Node) -> usize {
+        self.neighbors(adj, node).count()
+    }
+}
@@ -26,6 +26,15 @@ pub trait Hypergraph<'a> {
     fn add_hyperedge(&mut self, edge: Self::Edge);
 }
 
+/// Hypergraphs that can copy their nodes and hyperedges into an owned, growable hypergraph.
+///
+/// Algorithms that build a new hypergraph produce `Owned`, so they also work on read-only views such as mapped snapshots.
+pub trait ToOwnedHypergraph<'a>: Hypergraph<'a> {
+    type Owned: Hypergraph<'a>;
+    fn to_owned_node(node: &Self::Node) -> <Self::Owned as Hypergraph<'a>>::Node;
+    fn to_owned_edge(edge: &Self::Edge) -> <Self::Owned as Hypergraph<'a>>::Edge;
+}
+
 
 pub struct AdjacencyList<'a, T: Hypergraph<'a>>(HashMap<&'a T::Node, Vec<&'a T::Node>>);
 
@@ -131,10 +140,7 @@ where
         for (node, edges) in self.0.iter() {
             
             let s1 = format!("{}", node);
-            let mut s2 = String::new();
-            for edge in edges {
-                // s2.push_str(format!("{}, ", edge).as_str());
-            }
+            let s2 = edges.iter().map(|edge| format!("{:?}", edge.id())).collect::<Vec<_>>().join(", ");
 
             s.push_str(format!("Node {} -> {{{}}}\n", s1, s2).as_str());
         }
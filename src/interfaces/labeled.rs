@@ -2,8 +2,9 @@ use std::collections::HashMap;
 use std::fmt::Display;
 use std::hash::Hash;
 use crate::interfaces::graph::Graph;
+use crate::interfaces::similarity::LabelSimilarity;
 
-use crate::interfaces::graph::{Adjacency, SingleId, IdPair};
+use crate::interfaces::graph::{Adjacency, UnDirectedAdjacency, SingleId, IdPair};
 
 
 /// Label for `Node`
@@ -12,6 +13,9 @@ pub trait Label: Hash + Eq + Clone + Display {
 }
 
 pub trait Labeled<'a>: Graph<'a>  {
+    /// Whether the data node `node` matches the pattern node `label`.
+    ///
+    /// Single-labeled graphs compare the labels for equality; multi-labeled graphs require the pattern's labels to be a subset of the node's.
     fn label_same(&self, node: &Self::Node, label: &Self::Node) -> bool;
     fn get_label(&'a self, node: &'a Self::Node) -> &'a impl Label;
     fn get_edges_pair_label(&'a self) -> impl Iterator<Item = (&'a Self::Node, &'a Self::Node, &'a impl Label)>;
@@ -19,6 +23,11 @@ pub trait Labeled<'a>: Graph<'a>  {
     fn edge_node_label_same(&self, src1: &Self::Node, edge1: &Self::Edge, dst1: &Self::Node, src2: &Self::Node, edge2: &Self::Edge, dst2: &Self::Node) -> bool;
 }
 
+/// Graphs whose edges carry a label.
+pub trait EdgeLabeled<'a>: Labeled<'a> {
+    fn get_edge_label(&'a self, edge: &'a Self::Edge) -> &'a impl Label;
+}
+
 impl Label for String {
     fn label(&self) -> &str {
         self.as_str()
@@ -28,10 +37,36 @@ impl Label for String {
 pub trait HyperLabeled<'a>: Labeled<'a> {
     type L: Label;
     fn set_same_label_fn(&mut self, f: Box<dyn Fn(&Self::L, &Self::L) -> bool>);
+    fn set_label_similarity(&mut self, similarity: Box<dyn LabelSimilarity<Self::L>>);
+}
+
+/// Graphs whose nodes hold a set of labels, with an inverted index from each label to its nodes.
+pub trait MultiLabeled<'a>: Labeled<'a> {
+    type L: Label;
+    fn node_labels(&'a self, node: &'a Self::Node) -> impl Iterator<Item = &'a Self::L>;
+    fn nodes_with_label(&'a self, label: &Self::L) -> impl Iterator<Item = &'a Self::Node>;
+    fn label_count(&'a self, label: &Self::L) -> usize {
+        self.nodes_with_label(label).count()
+    }
+    // Data nodes that can match `pattern`, found through the least frequent of its labels.
+    fn candidates(&'a self, pattern: &'a Self::Node) -> impl Iterator<Item = &'a Self::Node> {
+        let rarest = self.node_labels(pattern).min_by_key(|label| self.label_count(label));
+        let nodes: Vec<_> = match rarest {
+            Some(label) => self.nodes_with_label(label).filter(|node| self.label_same(node, pattern)).collect(),
+            None => self.nodes().collect(),
+        };
+        nodes.into_iter()
+    }
 }
 
 pub struct LabeledAdjacencyList<'a, T: Graph<'a>>(HashMap<&'a T::Node, Vec<(&'a T::Node, &'a T::Edge)>>);
 
+impl<'a, T: Graph<'a>> Default for LabeledAdjacencyList<'a, T> {
+    fn default() -> Self {
+        LabeledAdjacencyList(HashMap::new())
+    }
+}
+
 pub trait LabeledAdjacency<'a>: Adjacency<'a> + Labeled<'a> 
 where <Self as Graph<'a>>::Edge: IdPair {
     fn get_labeled_adj(&'a self) -> LabeledAdjacencyList<'a, Self> {
@@ -57,3 +92,216 @@ where <Self as Graph<'a>>::Edge: IdPair {
         adj.0.get(node).expect(format!("No node in adjacency table named {}", node).as_str()).iter().copied()
     }
 }
+
+pub trait LabeledUnDirectedAdjacency<'a>: UnDirectedAdjacency<'a> + Labeled<'a> {
+    fn get_labeled_neighbors(&'a self) -> LabeledAdjacencyList<'a, Self> {
+        let mut adj = HashMap::new();
+        for node in self.nodes() {
+            adj.insert(node, Vec::new());
+        }
+        for (u, edge, v) in self.get_edges_pair_with_edge() {
+            adj.get_mut(u).unwrap().push((v, edge));
+            if u != v {
+                adj.get_mut(v).unwrap().push((u, edge));
+            }
+        }
+        LabeledAdjacencyList(adj)
+    }
+    fn get_labeled_neighbor(&'a self, adj: &LabeledAdjacencyList<'a, Self>, node: &Self::Node) -> impl Iterator<Item = (&'a Self::Node, &'a Self::Edge)> {
+        adj.0.get(node).unwrap_or_else(|| panic!("No node in adjacency table named {}", node)).iter().copied()
+    }
+}
+
+/// Label lookups and statistics, kept up to date by the graph that owns it as nodes and edges are added.
+///
+/// Nodes and edges are referred to by their position in `nodes()` and `edges()`. A node may carry several labels, and
+/// pair and triple counts follow each edge from every label of its source to every label of its destination. An edge
+/// added before one of its endpoints is counted once that node arrives. Node ids are expected to be unique: a repeated
+/// id refers to its latest node from then on, and edges counted before keep the labels they were counted with.
+#[derive(Clone, Debug)]
+pub struct LabelIndex {
+    labels: Vec<String>,
+    label_ids: HashMap<String, usize>,
+    // The labels of the node at position `i` are `node_labels[label_offsets[i]..label_offsets[i + 1]]`.
+    label_offsets: Vec<usize>,
+    node_labels: Vec<usize>,
+    positions: HashMap<usize, usize>,
+    nodes: HashMap<usize, Vec<usize>>,
+    edges: HashMap<usize, Vec<usize>>,
+    pairs: HashMap<(usize, usize), usize>,
+    triples: HashMap<(usize, usize, usize), usize>,
+    // Edges waiting for an endpoint, as (source id, label, destination id), keyed by the missing endpoint's id.
+    pending: HashMap<usize, Vec<(usize, usize, usize)>>,
+    edge_count: usize,
+}
+
+impl Default for LabelIndex {
+    fn default() -> Self {
+        LabelIndex {
+            labels: Vec::new(),
+            label_ids: HashMap::new(),
+            label_offsets: vec![0],
+            node_labels: Vec::new(),
+            positions: HashMap::new(),
+            nodes: HashMap::new(),
+            edges: HashMap::new(),
+            pairs: HashMap::new(),
+            triples: HashMap::new(),
+            pending: HashMap::new(),
+            edge_count: 0,
+        }
+    }
+}
+
+impl LabelIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn intern(&mut self, label: &str) -> usize {
+        if let Some(&id) = self.label_ids.get(label) {
+            return id;
+        }
+        self.labels.push(label.to_string());
+        self.label_ids.insert(label.to_string(), self.labels.len() - 1);
+        self.labels.len() - 1
+    }
+
+    /// Record the next node, with node id `id`.
+    pub fn add_node(&mut self, id: usize, label: &str) {
+        self.add_node_labels(id, [label]);
+    }
+
+    /// Record the next node, with node id `id` and every label in `labels`.
+    pub fn add_node_labels<'l>(&mut self, id: usize, labels: impl IntoIterator<Item = &'l str>) {
+        let position = self.node_count();
+        for label in labels {
+            let label = self.intern(label);
+            self.node_labels.push(label);
+            self.nodes.entry(label).or_default().push(position);
+        }
+        self.label_offsets.push(self.node_labels.len());
+        self.positions.insert(id, position);
+        for (src, label, dst) in self.pending.remove(&id).unwrap_or_default() {
+            self.count(src, label, dst);
+        }
+    }
+
+    /// Record the next edge, from the node with id `src` to the node with id `dst`.
+    pub fn add_edge(&mut self, src: usize, label: &str, dst: usize) {
+        let label = self.intern(label);
+        self.edges.entry(label).or_default().push(self.edge_count);
+        self.edge_count += 1;
+        self.count(src, label, dst);
+    }
+
+    fn count(&mut self, src: usize, label: usize, dst: usize) {
+        match (self.positions.get(&src), self.positions.get(&dst)) {
+            (Some(&s), Some(&d)) => {
+                for i in self.label_offsets[s]..self.label_offsets[s + 1] {
+                    for j in self.label_offsets[d]..self.label_offsets[d + 1] {
+                        let (s, d) = (self.node_labels[i], self.node_labels[j]);
+                        *self.pairs.entry((s, d)).or_insert(0) += 1;
+                        *self.triples.entry((s, label, d)).or_insert(0) += 1;
+                    }
+                }
+            }
+            (None, _) => self.pending.entry(src).or_default().push((src, label, dst)),
+            (_, None) => self.pending.entry(dst).or_default().push((src, label, dst)),
+        }
+    }
+
+    fn id(&self, label: &str) -> Option<usize> {
+        self.label_ids.get(label).copied()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.label_offsets.len() - 1
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Positions of the nodes labeled `label`, ascending.
+    pub fn node_positions(&self, label: &str) -> &[usize] {
+        self.id(label).and_then(|id| self.nodes.get(&id)).map_or(&[], Vec::as_slice)
+    }
+
+    /// Positions of the edges labeled `label`, ascending.
+    pub fn edge_positions(&self, label: &str) -> &[usize] {
+        self.id(label).and_then(|id| self.edges.get(&id)).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn node_label_frequency(&self, label: &str) -> usize {
+        self.node_positions(label).len()
+    }
+
+    pub fn edge_label_frequency(&self, label: &str) -> usize {
+        self.edge_positions(label).len()
+    }
+
+    pub fn node_label_frequencies(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        self.nodes.iter().map(|(&label, nodes)| (self.labels[label].as_str(), nodes.len()))
+    }
+
+    pub fn edge_label_frequencies(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        self.edges.iter().map(|(&label, edges)| (self.labels[label].as_str(), edges.len()))
+    }
+
+    /// Number of edges from a node labeled `src` to a node labeled `dst`.
+    pub fn pair_count(&self, src: &str, dst: &str) -> usize {
+        match (self.id(src), self.id(dst)) {
+            (Some(s), Some(d)) => self.pairs.get(&(s, d)).copied().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    /// Number of edges labeled `edge` from a node labeled `src` to a node labeled `dst`.
+    pub fn triple_count(&self, src: &str, edge: &str, dst: &str) -> usize {
+        match (self.id(src), self.id(edge), self.id(dst)) {
+            (Some(s), Some(e), Some(d)) => self.triples.get(&(s, e, d)).copied().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    pub fn pair_counts(&self) -> impl Iterator<Item = ((&str, &str), usize)> + '_ {
+        self.pairs.iter().map(|(&(s, d), &count)| ((self.labels[s].as_str(), self.labels[d].as_str()), count))
+    }
+
+    /// Fraction of the nodes carrying `label`.
+    pub fn selectivity(&self, label: &str) -> f64 {
+        if self.node_count() == 0 {
+            return 0.0;
+        }
+        self.node_label_frequency(label) as f64 / self.node_count() as f64
+    }
+
+    /// Fraction of the edges going from a `src`-labeled node to a `dst`-labeled node.
+    pub fn pair_selectivity(&self, src: &str, dst: &str) -> f64 {
+        if self.edge_count == 0 {
+            return 0.0;
+        }
+        self.pair_count(src, dst) as f64 / self.edge_count as f64
+    }
+
+    /// The least frequent of `labels`, a good place to start matching.
+    pub fn rarest<'l>(&self, labels: impl IntoIterator<Item = &'l str>) -> Option<&'l str> {
+        labels.into_iter().min_by_key(|label| self.node_label_frequency(label))
+    }
+}
+
+/// Graphs that maintain a `LabelIndex` over their node and edge labels.
+pub trait LabelStatistics<'a>: EdgeLabeled<'a> {
+    fn label_index(&self) -> &LabelIndex;
+    fn node_at(&'a self, position: usize) -> &'a Self::Node;
+    fn edge_at(&'a self, position: usize) -> &'a Self::Edge;
+
+    fn nodes_labeled(&'a self, label: &str) -> impl Iterator<Item = &'a Self::Node> {
+        self.label_index().node_positions(label).iter().map(move |&i| self.node_at(i))
+    }
+
+    fn edges_labeled(&'a self, label: &str) -> impl Iterator<Item = &'a Self::Edge> {
+        self.label_index().edge_positions(label).iter().map(move |&i| self.edge_at(i))
+    }
+}
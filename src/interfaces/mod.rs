@@ -4,4 +4,5 @@ pub mod labeled;
 pub mod hypergraph;
 pub mod vertex;
 pub mod edge;
-pub mod typed;
\ No newline at end of file
+pub mod typed;
+pub mod similarity;
\ No newline at end of file
@@ -0,0 +1,18 @@
+/// Decides whether two labels should be treated as the same label during matching.
+pub trait LabelSimilarity<L> {
+    fn similar(&self, x: &L, y: &L) -> bool;
+}
+
+// Plain closures (and the boxed closures taken by `HyperLabeled::set_same_label_fn`) are comparators too.
+impl<L, F> LabelSimilarity<L> for F
+where F: Fn(&L, &L) -> bool {
+    fn similar(&self, x: &L, y: &L) -> bool {
+        self(x, y)
+    }
+}
+
+/// Values that carry a descriptor embedding, of any dimension.
+pub trait Embedding {
+    type Scalar: Copy + Into<f64>;
+    fn embedding(&self) -> &[Self::Scalar];
+}
@@ -0,0 +1,657 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::impls::hypergraph::{Desc, HyperedgeImpl, HypergraphImpl, Node, NodeType};
+use crate::impls::standard::{LabelNode, LabeledEdge, SingleLabel};
+use crate::interfaces::edge::Weighted;
+use crate::interfaces::graph::{Directed, Graph, IdPair, SingleId, UnDirected};
+use crate::interfaces::hypergraph::{Hypergraph, IdVector};
+use crate::interfaces::labeled::Label;
+use crate::interfaces::typed::Type;
+
+use super::invalid_data;
+
+/// A typed GraphML attribute value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttrValue {
+    String(String),
+    Int(i64),
+    Double(f64),
+    Boolean(bool),
+}
+
+impl AttrValue {
+    fn type_name(&self) -> &'static str {
+        match self {
+            AttrValue::String(_) => "string",
+            AttrValue::Int(_) => "long",
+            AttrValue::Double(_) => "double",
+            AttrValue::Boolean(_) => "boolean",
+        }
+    }
+
+    fn parse(ty: &str, text: &str) -> io::Result<AttrValue> {
+        let text = text.trim();
+        let bad = || invalid_data(format!("`{}` is not a valid GraphML {}", text, ty));
+        match ty {
+            "string" => Ok(AttrValue::String(text.to_string())),
+            "int" | "long" => text.parse().map(AttrValue::Int).map_err(|_| bad()),
+            "float" | "double" => text.parse().map(AttrValue::Double).map_err(|_| bad()),
+            "boolean" => match text.to_ascii_lowercase().as_str() {
+                "true" | "1" => Ok(AttrValue::Boolean(true)),
+                "false" | "0" => Ok(AttrValue::Boolean(false)),
+                _ => Err(bad()),
+            },
+            _ => Err(invalid_data(format!("unknown GraphML attribute type `{}`", ty))),
+        }
+    }
+}
+
+impl std::fmt::Display for AttrValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttrValue::String(s) => write!(f, "{}", s),
+            AttrValue::Int(i) => write!(f, "{}", i),
+            AttrValue::Double(d) => write!(f, "{}", d),
+            AttrValue::Boolean(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+pub type Attributes = HashMap<String, AttrValue>;
+
+/// Attributes beyond the label. Nodes are keyed by node id, edges (and hyperedges) by their position in iteration order.
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct GraphAttributes {
+    pub nodes: HashMap<usize, Attributes>,
+    pub edges: HashMap<usize, Attributes>,
+}
+
+/// Nodes that can be built from a GraphML `<node>`.
+pub trait GraphMLNode: Sized {
+    fn from_graphml(id: usize, label: &str) -> Self;
+}
+
+/// Edges that can be built from a GraphML `<edge>`.
+pub trait GraphMLEdge: Sized {
+    fn from_graphml(src: usize, dst: usize, label: &str) -> Self;
+}
+
+impl GraphMLNode for LabelNode<String> {
+    fn from_graphml(id: usize, label: &str) -> Self {
+        LabelNode::new(id as u64, label.to_string())
+    }
+}
+
+impl GraphMLEdge for LabeledEdge<String> {
+    fn from_graphml(src: usize, dst: usize, label: &str) -> Self {
+        LabeledEdge::new(src as u64, dst as u64, label.to_string())
+    }
+}
+
+impl GraphMLEdge for LabeledEdge<SingleLabel> {
+    fn from_graphml(src: usize, dst: usize, _: &str) -> Self {
+        LabeledEdge::new(src as u64, dst as u64, SingleLabel::default())
+    }
+}
+
+const LABEL: &str = "label";
+const TYPE: &str = "type";
+const NODE_DESC: &str = "desc";
+const WEIGHT: &str = "weight";
+
+struct Key {
+    id: String,
+    domain: &'static str,
+    name: String,
+    ty: &'static str,
+}
+
+// Assign one `<key>` per (domain, attribute name), checking that every value of an attribute has the same type.
+// The keys already declared for `domain` are reserved (e.g. `label`), so attributes may not reuse their names.
+fn collect_keys<'b>(domain: &'static str, attributes: impl Iterator<Item = &'b Attributes>, keys: &mut Vec<Key>) -> io::Result<()> {
+    let reserved = keys.iter().filter(|key| key.domain == domain).map(|key| key.name.clone()).collect::<Vec<_>>();
+    for attrs in attributes {
+        let mut names = attrs.keys().collect::<Vec<_>>();
+        names.sort();
+        for name in names {
+            if reserved.contains(name) {
+                return Err(invalid_data(format!("{} attribute `{}` uses a reserved key name", domain, name)));
+            }
+            let ty = attrs[name].type_name();
+            match keys.iter().find(|key| key.domain == domain && &key.name == name) {
+                Some(key) if key.ty != ty => {
+                    return Err(invalid_data(format!("{} attribute `{}` is both {} and {}", domain, name, key.ty, ty)));
+                }
+                Some(_) => {}
+                None => keys.push(Key { id: format!("d{}", keys.len()), domain, name: name.clone(), ty }),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_header(writer: &mut impl Write, keys: &[Key]) -> io::Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+    for key in keys {
+        writeln!(writer, r#"  <key id="{}" for="{}" attr.name="{}" attr.type="{}"/>"#, key.id, key.domain, escape(key.name.as_str()), key.ty)?;
+    }
+    Ok(())
+}
+
+fn write_data(writer: &mut impl Write, keys: &[Key], domain: &str, name: &str, value: &str) -> io::Result<()> {
+    let key = keys.iter().find(|key| key.domain == domain && key.name == name).unwrap();
+    writeln!(writer, r#"    <data key="{}">{}</data>"#, key.id, escape(value))
+}
+
+fn write_attributes(writer: &mut impl Write, keys: &[Key], domain: &str, attrs: Option<&Attributes>) -> io::Result<()> {
+    if let Some(attrs) = attrs {
+        let mut names = attrs.keys().collect::<Vec<_>>();
+        names.sort();
+        for name in names {
+            write_data(writer, keys, domain, name, &attrs[name].to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// Write a labeled directed graph as GraphML. Node and edge labels go to the `label` keys, other attributes to one key per name;
+/// an attribute named `label` is rejected.
+pub fn write_graphml<'a, G>(graph: &'a G, attributes: &GraphAttributes, writer: impl Write) -> io::Result<()>
+where G: Graph<'a> + Directed, G::Node: Label, G::Edge: Label {
+    write_labeled_graph(graph, attributes, "directed", writer)
+}
+
+/// Write a labeled undirected graph as GraphML with `edgedefault="undirected"`.
+pub fn write_undirected_graphml<'a, G>(graph: &'a G, attributes: &GraphAttributes, writer: impl Write) -> io::Result<()>
+where G: Graph<'a> + UnDirected, G::Node: Label, G::Edge: Label {
+    write_labeled_graph(graph, attributes, "undirected", writer)
+}
+
+fn write_labeled_graph<'a, G>(graph: &'a G, attributes: &GraphAttributes, edgedefault: &str, mut writer: impl Write) -> io::Result<()>
+where G: Graph<'a>, G::Node: Label, G::Edge: Label {
+    let mut keys = vec![
+        Key { id: "label_n".to_string(), domain: "node", name: LABEL.to_string(), ty: "string" },
+        Key { id: "label_e".to_string(), domain: "edge", name: LABEL.to_string(), ty: "string" },
+    ];
+    collect_keys("node", attributes.nodes.values(), &mut keys)?;
+    collect_keys("edge", attributes.edges.values(), &mut keys)?;
+
+    write_header(&mut writer, &keys)?;
+    writeln!(writer, r#"  <graph id="G" edgedefault="{}">"#, edgedefault)?;
+    for node in graph.nodes() {
+        writeln!(writer, r#"   <node id="n{}">"#, node.id())?;
+        write_data(&mut writer, &keys, "node", LABEL, node.label())?;
+        write_attributes(&mut writer, &keys, "node", attributes.nodes.get(&node.id()))?;
+        writeln!(writer, "   </node>")?;
+    }
+    for (i, edge) in graph.edges().enumerate() {
+        let (src, dst) = edge.pair();
+        writeln!(writer, r#"   <edge id="e{}" source="n{}" target="n{}">"#, i, src, dst)?;
+        write_data(&mut writer, &keys, "edge", LABEL, edge.label())?;
+        write_attributes(&mut writer, &keys, "edge", attributes.edges.get(&i))?;
+        writeln!(writer, "   </edge>")?;
+    }
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</graphml>")
+}
+
+/// Write a hypergraph as GraphML, using `<hyperedge>` elements. Node types and descriptors are stored as `type` and `desc` keys,
+/// hyperedge weights, labels and types as `weight`, `label` and `type` keys. Attributes may not reuse those names.
+pub fn write_hypergraph_graphml(hypergraph: &HypergraphImpl, attributes: &GraphAttributes, mut writer: impl Write) -> io::Result<()> {
+    let mut keys = vec![
+        Key { id: "type_n".to_string(), domain: "node", name: TYPE.to_string(), ty: "long" },
+        Key { id: "desc_n".to_string(), domain: "node", name: NODE_DESC.to_string(), ty: "string" },
+        Key { id: "weight_h".to_string(), domain: "hyperedge", name: WEIGHT.to_string(), ty: "double" },
+        Key { id: "label_h".to_string(), domain: "hyperedge", name: LABEL.to_string(), ty: "string" },
+        Key { id: "type_h".to_string(), domain: "hyperedge", name: TYPE.to_string(), ty: "long" },
+    ];
+    collect_keys("node", attributes.nodes.values(), &mut keys)?;
+    collect_keys("hyperedge", attributes.edges.values(), &mut keys)?;
+
+    write_header(&mut writer, &keys)?;
+    writeln!(writer, r#"  <graph id="H" edgedefault="undirected">"#)?;
+    for node in hypergraph.nodes() {
+        writeln!(writer, r#"   <node id="n{}">"#, node.id())?;
+        write_data(&mut writer, &keys, "node", TYPE, &node.node_type().type_id().to_string())?;
+        let desc = node.desc().values().iter().map(|v| v.to_string()).collect::<Vec<_>>().join(" ");
+        write_data(&mut writer, &keys, "node", NODE_DESC, &desc)?;
+        write_attributes(&mut writer, &keys, "node", attributes.nodes.get(&node.id()))?;
+        writeln!(writer, "   </node>")?;
+    }
+    for (i, edge) in hypergraph.hyperedges().enumerate() {
+        writeln!(writer, r#"   <hyperedge id="h{}">"#, i)?;
+        for id in edge.id() {
+            writeln!(writer, r#"    <endpoint node="n{}"/>"#, id)?;
+        }
+        write_data(&mut writer, &keys, "hyperedge", WEIGHT, &edge.weight().to_string())?;
+        write_data(&mut writer, &keys, "hyperedge", LABEL, edge.label())?;
+        write_data(&mut writer, &keys, "hyperedge", TYPE, &edge.type_id().to_string())?;
+        write_attributes(&mut writer, &keys, "hyperedge", attributes.edges.get(&i))?;
+        writeln!(writer, "   </hyperedge>")?;
+    }
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</graphml>")
+}
+
+struct KeyDecl {
+    domain: String,
+    name: String,
+    ty: String,
+    default: Option<String>,
+}
+
+#[derive(Default)]
+struct Element {
+    ends: Vec<String>,
+    // The `directed` attribute of an `<edge>`, overriding the graph's `edgedefault`.
+    directed: Option<bool>,
+    data: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct Document {
+    keys: HashMap<String, KeyDecl>,
+    undirected: bool,
+    nodes: Vec<(String, Element)>,
+    edges: Vec<Element>,
+    hyperedges: Vec<Element>,
+}
+
+enum Owner {
+    None,
+    Key(String),
+    Element,
+}
+
+fn attr(e: &BytesStart, name: &str) -> io::Result<Option<String>> {
+    for a in e.attributes() {
+        let a = a.map_err(|err| invalid_data(err.to_string()))?;
+        if a.key.as_ref() == name.as_bytes() {
+            return Ok(Some(a.unescape_value().map_err(|err| invalid_data(err.to_string()))?.into_owned()));
+        }
+    }
+    Ok(None)
+}
+
+fn required_attr(e: &BytesStart, name: &str) -> io::Result<String> {
+    attr(e, name)?.ok_or_else(|| invalid_data(format!("<{}> is missing `{}`", String::from_utf8_lossy(e.name().as_ref()), name)))
+}
+
+fn parse_document(reader: impl BufRead) -> io::Result<Document> {
+    let mut reader = Reader::from_reader(reader);
+    let mut buf = Vec::new();
+    let mut doc = Document::default();
+    let mut owner = Owner::None;
+    let mut element = Element::default();
+    let mut node_id = String::new();
+    // The `<data>` key (or `<default>`) whose text is being read.
+    let mut text: Option<(Option<String>, String)> = None;
+
+    loop {
+        let event = reader.read_event_into(&mut buf).map_err(|err| invalid_data(err.to_string()))?;
+        let empty = matches!(event, Event::Empty(_));
+        match event {
+            Event::Start(e) | Event::Empty(e) => {
+                match e.local_name().as_ref() {
+                    b"key" => {
+                        let id = required_attr(&e, "id")?;
+                        doc.keys.insert(id.clone(), KeyDecl {
+                            domain: attr(&e, "for")?.unwrap_or_else(|| "all".to_string()),
+                            name: attr(&e, "attr.name")?.unwrap_or_else(|| id.clone()),
+                            ty: attr(&e, "attr.type")?.unwrap_or_else(|| "string".to_string()),
+                            default: None,
+                        });
+                        if !empty {
+                            owner = Owner::Key(id);
+                        }
+                    }
+                    b"default" if !empty => text = Some((None, String::new())),
+                    b"graph" => doc.undirected = attr(&e, "edgedefault")?.as_deref() == Some("undirected"),
+                    b"node" => {
+                        node_id = required_attr(&e, "id")?;
+                        if empty {
+                            doc.nodes.push((node_id.clone(), Element::default()));
+                        } else {
+                            owner = Owner::Element;
+                        }
+                    }
+                    b"edge" => {
+                        element.ends = vec![required_attr(&e, "source")?, required_attr(&e, "target")?];
+                        element.directed = match attr(&e, "directed")?.as_deref() {
+                            None => None,
+                            Some("true") => Some(true),
+                            Some("false") => Some(false),
+                            Some(other) => return Err(invalid_data(format!("`{}` is not a valid edge direction", other))),
+                        };
+                        if empty {
+                            doc.edges.push(std::mem::take(&mut element));
+                        } else {
+                            owner = Owner::Element;
+                        }
+                    }
+                    b"hyperedge" => {
+                        if empty {
+                            doc.hyperedges.push(Element::default());
+                        } else {
+                            owner = Owner::Element;
+                        }
+                    }
+                    b"endpoint" => element.ends.push(required_attr(&e, "node")?),
+                    b"data" => {
+                        let key = required_attr(&e, "key")?;
+                        if empty {
+                            element.data.insert(key, String::new());
+                        } else {
+                            text = Some((Some(key), String::new()));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Text(e) => {
+                if let Some((_, s)) = text.as_mut() {
+                    s.push_str(&e.unescape().map_err(|err| invalid_data(err.to_string()))?);
+                }
+            }
+            Event::CData(e) => {
+                if let Some((_, s)) = text.as_mut() {
+                    s.push_str(&String::from_utf8_lossy(&e));
+                }
+            }
+            Event::End(e) => {
+                match e.local_name().as_ref() {
+                    b"data" => {
+                        if let Some((Some(key), s)) = text.take() {
+                            element.data.insert(key, s);
+                        }
+                    }
+                    b"default" => {
+                        if let (Owner::Key(id), Some((None, s))) = (&owner, text.take()) {
+                            doc.keys.get_mut(id).unwrap().default = Some(s);
+                        }
+                    }
+                    b"key" => owner = Owner::None,
+                    b"node" => {
+                        doc.nodes.push((std::mem::take(&mut node_id), std::mem::take(&mut element)));
+                        owner = Owner::None;
+                    }
+                    b"edge" => {
+                        doc.edges.push(std::mem::take(&mut element));
+                        owner = Owner::None;
+                    }
+                    b"hyperedge" => {
+                        doc.hyperedges.push(std::mem::take(&mut element));
+                        owner = Owner::None;
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        if let Owner::None = owner {
+            // Data directly under <graph> or <graphml> is not attached to anything we model.
+            element.data.clear();
+        }
+        buf.clear();
+    }
+    Ok(doc)
+}
+
+impl Document {
+    // GraphML ids written as `n<k>` (or plain `<k>`) keep `k` as the node id; otherwise nodes are numbered in document order.
+    fn node_ids(&self) -> io::Result<HashMap<String, usize>> {
+        let parsed = self.nodes.iter()
+            .map(|(id, _)| id.strip_prefix('n').unwrap_or(id).parse::<usize>().ok().map(|k| (id.clone(), k)))
+            .collect::<Option<HashMap<_, _>>>();
+        let ids = match parsed {
+            Some(ids) if ids.len() == self.nodes.len() && {
+                let mut values = ids.values().collect::<Vec<_>>();
+                values.sort();
+                values.dedup();
+                values.len() == ids.len()
+            } => ids,
+            _ => self.nodes.iter().enumerate().map(|(i, (id, _))| (id.clone(), i)).collect(),
+        };
+        if ids.len() != self.nodes.len() {
+            return Err(invalid_data("duplicate GraphML node id"));
+        }
+        Ok(ids)
+    }
+
+    fn is_directed(&self, edge: &Element) -> bool {
+        edge.directed.unwrap_or(!self.undirected)
+    }
+
+    fn resolve(&self, ids: &HashMap<String, usize>, end: &str) -> io::Result<usize> {
+        ids.get(end).copied().ok_or_else(|| invalid_data(format!("reference to unknown node `{}`", end)))
+    }
+
+    // Split the `<data>` of one element into the reserved keys and typed user attributes, applying key defaults.
+    fn attributes(&self, domain: &str, element: &Element, reserved: &[&str]) -> io::Result<(HashMap<String, String>, Attributes)> {
+        let mut special = HashMap::new();
+        let mut attrs = Attributes::new();
+        for (id, key) in self.keys.iter() {
+            if key.domain != domain && key.domain != "all" {
+                continue;
+            }
+            let value = match element.data.get(id).or(key.default.as_ref()) {
+                Some(value) => value,
+                None => continue,
+            };
+            if reserved.contains(&key.name.as_str()) {
+                special.insert(key.name.clone(), value.clone());
+            } else {
+                attrs.insert(key.name.clone(), AttrValue::parse(&key.ty, value)?);
+            }
+        }
+        Ok((special, attrs))
+    }
+}
+
+/// Read a GraphML graph into a directed graph. The `label` keys become node and edge labels; every other key is returned as a typed attribute.
+/// Undirected edges (from `edgedefault="undirected"` or `directed="false"`) are added in both directions, each copy carrying the
+/// edge's attributes, so edge positions follow document order only when every edge is directed.
+pub fn read_graphml<'a, G>(reader: impl BufRead) -> io::Result<(G, GraphAttributes)>
+where G: Graph<'a> + Directed, G::Node: GraphMLNode, G::Edge: GraphMLEdge {
+    read_labeled_graph(parse_document(reader)?, true)
+}
+
+/// Read a GraphML graph into an undirected graph. Directed edges are rejected, since their orientation would be lost.
+pub fn read_undirected_graphml<'a, G>(reader: impl BufRead) -> io::Result<(G, GraphAttributes)>
+where G: Graph<'a> + UnDirected, G::Node: GraphMLNode, G::Edge: GraphMLEdge {
+    read_labeled_graph(parse_document(reader)?, false)
+}
+
+fn read_labeled_graph<'a, G>(doc: Document, directed: bool) -> io::Result<(G, GraphAttributes)>
+where G: Graph<'a>, G::Node: GraphMLNode, G::Edge: GraphMLEdge {
+    let ids = doc.node_ids()?;
+    let mut graph = G::new();
+    let mut attributes = GraphAttributes::default();
+
+    for (name, element) in doc.nodes.iter() {
+        let id = ids[name];
+        let (special, attrs) = doc.attributes("node", element, &[LABEL])?;
+        graph.add_node(G::Node::from_graphml(id, special.get(LABEL).map(String::as_str).unwrap_or("")));
+        if !attrs.is_empty() {
+            attributes.nodes.insert(id, attrs);
+        }
+    }
+    let mut position = 0;
+    for element in doc.edges.iter() {
+        let (src, dst) = (doc.resolve(&ids, &element.ends[0])?, doc.resolve(&ids, &element.ends[1])?);
+        let (special, attrs) = doc.attributes("edge", element, &[LABEL])?;
+        let label = special.get(LABEL).map(String::as_str).unwrap_or("");
+        let mut pairs = vec![(src, dst)];
+        match (directed, doc.is_directed(element)) {
+            (true, false) if src != dst => pairs.push((dst, src)),
+            (false, true) => return Err(invalid_data(format!("directed edge from `{}` to `{}` in an undirected graph", element.ends[0], element.ends[1]))),
+            _ => {}
+        }
+        for (src, dst) in pairs {
+            graph.add_edge(G::Edge::from_graphml(src, dst, label));
+            if !attrs.is_empty() {
+                attributes.edges.insert(position, attrs.clone());
+            }
+            position += 1;
+        }
+    }
+    Ok((graph, attributes))
+}
+
+/// Read a GraphML hypergraph from its `<hyperedge>` elements. Missing `type` and `desc` keys give the default node type and descriptor;
+/// missing hyperedge keys give weight 1, an empty label and type 0.
+pub fn read_hypergraph_graphml(reader: impl BufRead) -> io::Result<(HypergraphImpl, GraphAttributes)> {
+    let doc = parse_document(reader)?;
+    let ids = doc.node_ids()?;
+    let mut hypergraph = HypergraphImpl::new();
+    let mut attributes = GraphAttributes::default();
+
+    for (name, element) in doc.nodes.iter() {
+        let id = ids[name];
+        let (special, attrs) = doc.attributes("node", element, &[TYPE, NODE_DESC])?;
+        let node_type = match special.get(TYPE) {
+            Some(t) => NodeType::new(t.trim().parse().map_err(|_| invalid_data(format!("invalid node type `{}`", t)))?),
+            None => NodeType::new(0),
+        };
+        let desc = match special.get(NODE_DESC) {
+            Some(d) => {
+                let values = super::parse_fields::<f64>(d)?;
+                Desc::new(values.try_into().map_err(|_| invalid_data(format!("descriptor of node `{}` must have 16 values", name)))?)
+            }
+            None => Desc::default(),
+        };
+        hypergraph.add_node(Node::new(id, node_type, desc));
+        if !attrs.is_empty() {
+            attributes.nodes.insert(id, attrs);
+        }
+    }
+    for (i, element) in doc.hyperedges.iter().enumerate() {
+        let nodes = element.ends.iter().map(|end| doc.resolve(&ids, end)).collect::<io::Result<Vec<_>>>()?;
+        let (special, attrs) = doc.attributes("hyperedge", element, &[WEIGHT, LABEL, TYPE])?;
+        let weight = match special.get(WEIGHT) {
+            Some(w) => w.trim().parse().map_err(|_| invalid_data(format!("invalid hyperedge weight `{}`", w)))?,
+            None => 1.0,
+        };
+        let edge_type = match special.get(TYPE) {
+            Some(t) => t.trim().parse().map_err(|_| invalid_data(format!("invalid hyperedge type `{}`", t)))?,
+            None => 0,
+        };
+        let label = special.get(LABEL).cloned().unwrap_or_default();
+        hypergraph.add_hyperedge(HyperedgeImpl::new(nodes, weight, label, edge_type));
+        if !attrs.is_empty() {
+            attributes.edges.insert(i, attrs);
+        }
+    }
+    Ok((hypergraph, attributes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::standard::{StandardLabeledGraph, StandardUnDirectedGraph};
+
+    #[test]
+    fn labeled_graph_round_trip() {
+        let mut graph = StandardLabeledGraph::new();
+        graph.add_node(0, "a & b".to_string());
+        graph.add_node(3, "c".to_string());
+        graph.add_edge(0, 3);
+
+        let mut attributes = GraphAttributes::default();
+        attributes.nodes.insert(3, Attributes::from([
+            ("weight".to_string(), AttrValue::Double(0.5)),
+            ("visited".to_string(), AttrValue::Boolean(true)),
+        ]));
+        attributes.edges.insert(0, Attributes::from([("capacity".to_string(), AttrValue::Int(7))]));
+
+        let mut out = Vec::new();
+        write_graphml(&graph, &attributes, &mut out).unwrap();
+        let (read, read_attributes): (StandardLabeledGraph, _) = read_graphml(out.as_slice()).unwrap();
+
+        assert_eq!(read.to_string(), graph.to_string());
+        assert_eq!(read_attributes, attributes);
+
+        attributes.edges.insert(0, Attributes::from([("label".to_string(), AttrValue::String("x".to_string()))]));
+        assert!(write_graphml(&graph, &attributes, Vec::new()).is_err());
+    }
+
+    #[test]
+    fn hypergraph_round_trip() {
+        let mut hypergraph = HypergraphImpl::new();
+        hypergraph.add_node(Node::new(0, NodeType::new(2), Desc::default()));
+        hypergraph.add_node(Node::new(4, NodeType::new(0), Desc::default()));
+        hypergraph.add_node(Node::new(7, NodeType::new(1), Desc::default()));
+        hypergraph.add_hyperedge(HyperedgeImpl::new(vec![0, 4, 7], 2.5, "cites".to_string(), 3));
+        hypergraph.add_hyperedge(HyperedgeImpl::new(vec![7], 1.0, String::new(), 0));
+
+        let mut attributes = GraphAttributes::default();
+        attributes.edges.insert(1, Attributes::from([("year".to_string(), AttrValue::Int(2024))]));
+
+        let mut out = Vec::new();
+        write_hypergraph_graphml(&hypergraph, &attributes, &mut out).unwrap();
+        let (read, read_attributes) = read_hypergraph_graphml(out.as_slice()).unwrap();
+
+        assert!(read == hypergraph);
+        let edges = read.hyperedges().collect::<Vec<_>>();
+        assert_eq!((edges[0].weight(), edges[0].label(), edges[0].type_id()), (2.5, "cites", 3));
+        assert_eq!(read.nodes().map(|n| n.node_type().type_id()).collect::<Vec<_>>(), vec![2, 0, 1]);
+        assert_eq!(read_attributes, attributes);
+    }
+
+    #[test]
+    fn key_defaults_and_int_attributes() {
+        let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="l" for="node" attr.name="label" attr.type="string"><default>anon</default></key>
+  <key id="k" for="edge" attr.name="hops" attr.type="int"><default>1</default></key>
+  <graph id="G" edgedefault="directed">
+    <node id="n0"><data key="l">a</data></node>
+    <node id="n1"/>
+    <edge source="n0" target="n1"><data key="k">-3</data></edge>
+    <edge source="n1" target="n0"/>
+  </graph>
+</graphml>"#;
+        let (graph, attributes): (StandardLabeledGraph, _) = read_graphml(input.as_bytes()).unwrap();
+        assert_eq!(graph.nodes().map(|n| n.label().to_string()).collect::<Vec<_>>(), vec!["a", "anon"]);
+        assert_eq!(attributes.edges[&0]["hops"], AttrValue::Int(-3));
+        assert_eq!(attributes.edges[&1]["hops"], AttrValue::Int(1));
+
+        let bad = input.replace("-3", "three");
+        assert!(read_graphml::<StandardLabeledGraph>(bad.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn undirected_edges() {
+        let input = r#"<graphml>
+  <graph edgedefault="undirected">
+    <node id="n0"/><node id="n1"/><node id="n2"/>
+    <edge source="n0" target="n1"/>
+    <edge source="n2" target="n2"/>
+    <edge source="n1" target="n2" directed="true"/>
+  </graph>
+</graphml>"#;
+        // A directed graph gets both orientations of every undirected edge except self-loops.
+        let (directed, _): (StandardLabeledGraph, _) = read_graphml(input.as_bytes()).unwrap();
+        let pairs = directed.edges().map(|e| e.pair()).collect::<Vec<_>>();
+        assert_eq!(pairs, vec![(0, 1), (1, 0), (2, 2), (1, 2)]);
+
+        // An undirected graph keeps one edge each, but cannot hold the directed one.
+        assert!(read_undirected_graphml::<StandardUnDirectedGraph>(input.as_bytes()).is_err());
+        let input = input.replace(r#" directed="true""#, "");
+        let (undirected, _): (StandardUnDirectedGraph, _) = read_undirected_graphml(input.as_bytes()).unwrap();
+        assert_eq!(undirected.edges().count(), 3);
+
+        let mut out = Vec::new();
+        write_undirected_graphml(&undirected, &GraphAttributes::default(), &mut out).unwrap();
+        assert!(String::from_utf8(out.clone()).unwrap().contains(r#"edgedefault="undirected""#));
+        let (again, _): (StandardUnDirectedGraph, _) = read_undirected_graphml(out.as_slice()).unwrap();
+        assert_eq!(again.to_string(), undirected.to_string());
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use crate::impls::hypergraph::{HyperedgeImpl, HypergraphImpl, Node};
+use crate::interfaces::graph::SingleId;
+use crate::interfaces::hypergraph::{Hypergraph, IdVector};
+
+use super::{check_weights, data_lines, invalid_data, parse_fields, Weights};
+
+/// Read a hypergraph in the hMETIS `.hgr` format.
+///
+/// The header is `<hyperedges> <nodes> [fmt]`, where `fmt` is `1` for hyperedge weights, `10` for node weights and `11` for both.
+/// Nodes are numbered from 1 in the file and become nodes `0..n` with the default type and descriptor.
+pub fn read_hmetis(reader: impl BufRead) -> io::Result<(HypergraphImpl, Weights)> {
+    let mut lines = data_lines(reader);
+    let header = lines.next().ok_or_else(|| invalid_data("missing hMETIS header"))??;
+    let header: Vec<usize> = parse_fields(&header)?;
+    let (m, n, fmt) = match header.as_slice() {
+        [m, n] => (*m, *n, 0),
+        [m, n, fmt] => (*m, *n, *fmt),
+        _ => return Err(invalid_data("hMETIS header must be `<hyperedges> <nodes> [fmt]`")),
+    };
+    if fmt % 10 > 1 || fmt / 10 > 1 {
+        return Err(invalid_data(format!("unknown hMETIS fmt `{}`", fmt)));
+    }
+    let (has_edge_weights, has_node_weights) = (fmt % 10 == 1, fmt / 10 == 1);
+
+    let mut hypergraph = HypergraphImpl::new();
+    for id in 0..n {
+        hypergraph.add_node(Node::from_id(id));
+    }
+
+    let mut edge_weights = Vec::new();
+    for i in 0..m {
+        let line = lines.next().ok_or_else(|| invalid_data(format!("missing hyperedge {}", i + 1)))??;
+        let mut fields: Vec<i64> = parse_fields(&line)?;
+        let mut weight = 1.0;
+        if has_edge_weights {
+            if fields.is_empty() {
+                return Err(invalid_data(format!("missing weight of hyperedge {}", i + 1)));
+            }
+            let w = fields.remove(0);
+            edge_weights.push(w);
+            weight = w as f64;
+        }
+        let pins = fields.into_iter().map(|pin| {
+            if pin < 1 || pin as usize > n {
+                Err(invalid_data(format!("node {} of hyperedge {} is out of range", pin, i + 1)))
+            } else {
+                Ok(pin as usize - 1)
+            }
+        }).collect::<io::Result<Vec<_>>>()?;
+        hypergraph.add_hyperedge(HyperedgeImpl::new(pins, weight, String::new(), 0));
+    }
+
+    let mut node_weights = Vec::new();
+    if has_node_weights {
+        for i in 0..n {
+            let line = lines.next().ok_or_else(|| invalid_data(format!("missing weight of node {}", i + 1)))??;
+            match parse_fields::<i64>(&line)?.as_slice() {
+                [w] => node_weights.push(*w),
+                _ => return Err(invalid_data(format!("node weight line {} must hold one value", i + 1))),
+            }
+        }
+    }
+
+    let weights = Weights::new(
+        has_node_weights.then_some(node_weights),
+        has_edge_weights.then_some(edge_weights),
+    );
+    Ok((hypergraph, weights))
+}
+
+/// Write any hypergraph in the hMETIS `.hgr` format, numbering nodes by their position in `nodes()`.
+/// Empty hyperedges are rejected: an unweighted empty hyperedge would be a blank line, which readers skip.
+pub fn write_hmetis<'a, H: Hypergraph<'a>>(hypergraph: &'a H, weights: &Weights, mut writer: impl Write) -> io::Result<()> {
+    let index: HashMap<usize, usize> = hypergraph.nodes().enumerate().map(|(i, node)| (node.id(), i + 1)).collect();
+    let m = hypergraph.hyperedges().count();
+    check_weights(&weights.nodes, index.len(), "node")?;
+    check_weights(&weights.hyperedges, m, "hyperedge")?;
+
+    let fmt = match (&weights.nodes, &weights.hyperedges) {
+        (None, None) => String::new(),
+        (None, Some(_)) => " 1".to_string(),
+        (Some(_), None) => " 10".to_string(),
+        (Some(_), Some(_)) => " 11".to_string(),
+    };
+    writeln!(writer, "{} {}{}", m, index.len(), fmt)?;
+
+    for (i, edge) in hypergraph.hyperedges().enumerate() {
+        let pins = edge.id();
+        if pins.is_empty() {
+            return Err(invalid_data(format!("hyperedge {} is empty", i + 1)));
+        }
+        let mut fields = Vec::new();
+        if let Some(w) = &weights.hyperedges {
+            fields.push(w[i].to_string());
+        }
+        for id in pins {
+            let pin = index.get(&id).ok_or_else(|| invalid_data(format!("hyperedge {} refers to unknown node {}", i, id)))?;
+            fields.push(pin.to_string());
+        }
+        writeln!(writer, "{}", fields.join(" "))?;
+    }
+
+    if let Some(w) = &weights.nodes {
+        for weight in w {
+            writeln!(writer, "{}", weight)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interfaces::edge::Weighted;
+
+    #[test]
+    fn round_trip_with_weights() {
+        let input = "% a comment\n4 7 11\n2 1 2\n3 1 7 5 6\n8 5 6 4\n7 2 3 4\n5\n1\n8\n7\n3\n9\n3\n";
+        let (hypergraph, weights) = read_hmetis(input.as_bytes()).unwrap();
+        assert_eq!(hypergraph.nodes().count(), 7);
+        assert_eq!(hypergraph.hyperedges().next().unwrap().id(), vec![0, 1]);
+        assert_eq!(weights.hyperedges, Some(vec![2, 3, 8, 7]));
+        assert_eq!(hypergraph.hyperedges().map(|e| e.weight()).collect::<Vec<_>>(), vec![2.0, 3.0, 8.0, 7.0]);
+        assert_eq!(weights.nodes, Some(vec![5, 1, 8, 7, 3, 9, 3]));
+
+        let mut out = Vec::new();
+        write_hmetis(&hypergraph, &weights, &mut out).unwrap();
+        let (again, again_weights) = read_hmetis(out.as_slice()).unwrap();
+        assert!(again == hypergraph);
+        assert_eq!(again_weights, weights);
+    }
+
+    #[test]
+    fn empty_hyperedges_are_not_written() {
+        let mut hypergraph = HypergraphImpl::new();
+        hypergraph.add_node(Node::from_id(0));
+        hypergraph.add_hyperedge(HyperedgeImpl::new(vec![], 1.0, String::new(), 0));
+        assert!(write_hmetis(&hypergraph, &Weights::default(), Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,50 @@
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+pub mod graphml;
+pub mod hmetis;
+pub mod patoh;
+// Snapshots map records in place, so they assume the on-disk layout matches the target.
+#[cfg(all(target_endian = "little", target_pointer_width = "64"))]
+pub mod snapshot;
+
+// Optional weights carried by the partitioner formats. Index `i` is the weight of the `i`-th node (or hyperedge) in iteration order.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Weights {
+    pub nodes: Option<Vec<i64>>,
+    pub hyperedges: Option<Vec<i64>>,
+}
+
+impl Weights {
+    pub fn new(nodes: Option<Vec<i64>>, hyperedges: Option<Vec<i64>>) -> Self {
+        Weights { nodes, hyperedges }
+    }
+}
+
+pub(crate) fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+// Lines of a text format with `%` comments and blank lines removed.
+pub(crate) fn data_lines(reader: impl BufRead) -> impl Iterator<Item = io::Result<String>> {
+    reader.lines().filter(|line| match line {
+        Ok(line) => {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('%')
+        }
+        Err(_) => true,
+    })
+}
+
+pub(crate) fn parse_fields<T: FromStr>(line: &str) -> io::Result<Vec<T>> {
+    line.split_whitespace()
+        .map(|field| field.parse::<T>().map_err(|_| invalid_data(format!("invalid number `{}`", field))))
+        .collect()
+}
+
+pub(crate) fn check_weights(weights: &Option<Vec<i64>>, expected: usize, what: &str) -> io::Result<()> {
+    match weights {
+        Some(w) if w.len() != expected => Err(invalid_data(format!("expected {} {} weights, got {}", expected, what, w.len()))),
+        _ => Ok(()),
+    }
+}
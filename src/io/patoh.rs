@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use crate::impls::hypergraph::{HyperedgeImpl, HypergraphImpl, Node};
+use crate::interfaces::graph::SingleId;
+use crate::interfaces::hypergraph::{Hypergraph, IdVector};
+
+use super::{check_weights, data_lines, invalid_data, parse_fields, Weights};
+
+/// Read a hypergraph in the PaToH format.
+///
+/// The header is `<base> <cells> <nets> <pins> [scheme [constraints]]`, where `base` is 0 or 1 and `scheme` is
+/// `1` for cell weights, `2` for net weights and `3` for both. Cells become nodes `0..cells` with the default type and descriptor.
+/// Only single-constraint cell weights are supported.
+pub fn read_patoh(reader: impl BufRead) -> io::Result<(HypergraphImpl, Weights)> {
+    let mut lines = data_lines(reader);
+    let header = lines.next().ok_or_else(|| invalid_data("missing PaToH header"))??;
+    let header: Vec<usize> = parse_fields(&header)?;
+    let (base, cells, nets, pins, scheme, constraints) = match header.as_slice() {
+        [b, c, n, p] => (*b, *c, *n, *p, 0, 1),
+        [b, c, n, p, s] => (*b, *c, *n, *p, *s, 1),
+        [b, c, n, p, s, k] => (*b, *c, *n, *p, *s, *k),
+        _ => return Err(invalid_data("PaToH header must be `<base> <cells> <nets> <pins> [scheme [constraints]]`")),
+    };
+    if base > 1 {
+        return Err(invalid_data(format!("PaToH index base must be 0 or 1, got {}", base)));
+    }
+    if scheme > 3 {
+        return Err(invalid_data(format!("unknown PaToH weight scheme `{}`", scheme)));
+    }
+    if constraints != 1 {
+        return Err(invalid_data(format!("{} cell weight constraints are not supported", constraints)));
+    }
+    let (has_cell_weights, has_net_weights) = (scheme & 1 == 1, scheme & 2 == 2);
+
+    let mut hypergraph = HypergraphImpl::new();
+    for id in 0..cells {
+        hypergraph.add_node(Node::from_id(id));
+    }
+
+    let mut net_weights = Vec::new();
+    let mut pin_count = 0;
+    for i in 0..nets {
+        let line = lines.next().ok_or_else(|| invalid_data(format!("missing net {}", i)))??;
+        let mut fields: Vec<i64> = parse_fields(&line)?;
+        let mut weight = 1.0;
+        if has_net_weights {
+            if fields.is_empty() {
+                return Err(invalid_data(format!("missing weight of net {}", i)));
+            }
+            let w = fields.remove(0);
+            net_weights.push(w);
+            weight = w as f64;
+        }
+        let members = fields.into_iter().map(|pin| {
+            let cell = pin - base as i64;
+            if cell < 0 || cell as usize >= cells {
+                Err(invalid_data(format!("cell {} of net {} is out of range", pin, i)))
+            } else {
+                Ok(cell as usize)
+            }
+        }).collect::<io::Result<Vec<_>>>()?;
+        pin_count += members.len();
+        hypergraph.add_hyperedge(HyperedgeImpl::new(members, weight, String::new(), 0));
+    }
+    if pin_count != pins {
+        return Err(invalid_data(format!("header declares {} pins, found {}", pins, pin_count)));
+    }
+
+    // Cell weights may be spread over any number of lines.
+    let mut cell_weights = Vec::new();
+    if has_cell_weights {
+        while cell_weights.len() < cells {
+            let line = lines.next().ok_or_else(|| invalid_data(format!("expected {} cell weights, got {}", cells, cell_weights.len())))??;
+            cell_weights.extend(parse_fields::<i64>(&line)?);
+        }
+        if cell_weights.len() != cells {
+            return Err(invalid_data(format!("expected {} cell weights, got {}", cells, cell_weights.len())));
+        }
+    }
+
+    let weights = Weights::new(
+        has_cell_weights.then_some(cell_weights),
+        has_net_weights.then_some(net_weights),
+    );
+    Ok((hypergraph, weights))
+}
+
+/// Write any hypergraph in the PaToH format with index base 0, numbering cells by their position in `nodes()`.
+/// Empty hyperedges are rejected: an unweighted empty net would be a blank line, which readers skip.
+pub fn write_patoh<'a, H: Hypergraph<'a>>(hypergraph: &'a H, weights: &Weights, mut writer: impl Write) -> io::Result<()> {
+    let index: HashMap<usize, usize> = hypergraph.nodes().enumerate().map(|(i, node)| (node.id(), i)).collect();
+    let nets = hypergraph.hyperedges().map(|edge| edge.id()).collect::<Vec<_>>();
+    check_weights(&weights.nodes, index.len(), "cell")?;
+    check_weights(&weights.hyperedges, nets.len(), "net")?;
+
+    let pins: usize = nets.iter().map(|net| net.len()).sum();
+    let scheme = weights.nodes.is_some() as usize | (weights.hyperedges.is_some() as usize) << 1;
+    writeln!(writer, "0 {} {} {} {}", index.len(), nets.len(), pins, scheme)?;
+
+    for (i, net) in nets.iter().enumerate() {
+        if net.is_empty() {
+            return Err(invalid_data(format!("net {} is empty", i)));
+        }
+        let mut fields = Vec::new();
+        if let Some(w) = &weights.hyperedges {
+            fields.push(w[i].to_string());
+        }
+        for id in net {
+            let cell = index.get(id).ok_or_else(|| invalid_data(format!("net {} refers to unknown node {}", i, id)))?;
+            fields.push(cell.to_string());
+        }
+        writeln!(writer, "{}", fields.join(" "))?;
+    }
+
+    if let Some(w) = &weights.nodes {
+        writeln!(writer, "{}", w.iter().map(|w| w.to_string()).collect::<Vec<_>>().join(" "))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interfaces::edge::Weighted;
+
+    #[test]
+    fn round_trip_with_weights() {
+        // 1-based, net weights and cell weights split over two lines.
+        let input = "% a comment\n1 4 3 7 3\n2 1 2\n5 2 3 4\n1 4 1\n3 1\n4 2\n";
+        let (hypergraph, weights) = read_patoh(input.as_bytes()).unwrap();
+        assert_eq!(hypergraph.nodes().count(), 4);
+        assert_eq!(hypergraph.hyperedges().map(|e| e.id()).collect::<Vec<_>>(), vec![vec![0, 1], vec![1, 2, 3], vec![3, 0]]);
+        assert_eq!(hypergraph.hyperedges().map(|e| e.weight()).collect::<Vec<_>>(), vec![2.0, 5.0, 1.0]);
+        assert_eq!(weights, Weights::new(Some(vec![3, 1, 4, 2]), Some(vec![2, 5, 1])));
+
+        let mut out = Vec::new();
+        write_patoh(&hypergraph, &weights, &mut out).unwrap();
+        let (again, again_weights) = read_patoh(out.as_slice()).unwrap();
+        assert!(again == hypergraph);
+        assert_eq!(again_weights, weights);
+    }
+
+    #[test]
+    fn empty_nets_are_not_written() {
+        let mut hypergraph = HypergraphImpl::new();
+        hypergraph.add_node(Node::from_id(0));
+        hypergraph.add_hyperedge(HyperedgeImpl::new(vec![], 1.0, String::new(), 0));
+        hypergraph.add_hyperedge(HyperedgeImpl::new(vec![0], 1.0, String::new(), 0));
+        assert!(write_patoh(&hypergraph, &Weights::default(), Vec::new()).is_err());
+    }
+}
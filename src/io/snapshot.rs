@@ -0,0 +1,862 @@
+// A snapshot is a little-endian file made of a fixed header followed by 8-byte aligned sections. Node records,
+// edge and hyperedge records, CSR offsets and hyperedge pins are read in place from the mapped file; only the
+// interned label table is decoded when a view is created. Opening a view checks the section bounds, not the indices
+// inside them: `validate` does that in O(n + m), and node, edge and hyperedge views are built on first use.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, Write};
+use std::mem::{align_of, size_of};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use memmap2::Mmap;
+
+use crate::impls::hypergraph::Node;
+use crate::impls::hypergraph::{HyperedgeImpl, HypergraphImpl};
+use crate::interfaces::edge::{Hyperedge, Weighted};
+use crate::interfaces::graph::{Adjacency, AdjacencyInv, AdjacencyList, Directed, Graph, IdPair, SingleId};
+use crate::interfaces::hypergraph::{Hypergraph, IdVector, ToOwnedHypergraph};
+use crate::interfaces::labeled::{EdgeLabeled, Label, LabelIndex, LabelStatistics, Labeled, LabeledAdjacency, LabeledAdjacencyList};
+use crate::interfaces::typed::Type;
+use crate::interfaces::vertex::Vertex;
+
+use super::invalid_data;
+
+const MAGIC: &[u8; 8] = b"GRAPHBS\0";
+/// Version 2 added hyperedge weights, labels and types, and a fifth header count.
+pub const VERSION: u32 = 2;
+const KIND_GRAPH: u32 = 1;
+const KIND_HYPERGRAPH: u32 = 2;
+const HEADER_LEN: usize = 56;
+
+const _: () = assert!(size_of::<Node>() == 144 && align_of::<Node>() == 8);
+
+/// Types that may be read directly from snapshot bytes.
+///
+/// # Safety
+///
+/// Implementors must be `repr(C)` without padding, and every bit pattern must be a valid value.
+unsafe trait Plain: Sized {}
+
+unsafe impl Plain for u64 {}
+unsafe impl Plain for usize {}
+unsafe impl Plain for Node {}
+unsafe impl Plain for SnapshotNode {}
+unsafe impl Plain for SnapshotEdge {}
+unsafe impl Plain for SnapshotHyperedge {}
+
+// The node and edge records of a graph snapshot. `label` indexes the interned label table.
+#[repr(C)]
+struct SnapshotNode {
+    id: u64,
+    label: u64,
+}
+
+#[repr(C)]
+struct SnapshotEdge {
+    src: u64,
+    dst: u64,
+    label: u64,
+}
+
+// The attributes of a hyperedge. The weight is stored as its bit pattern so records can be compared and hashed.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+#[repr(C)]
+struct SnapshotHyperedge {
+    weight: u64,
+    edge_type: u64,
+    label: u64,
+}
+
+/// An interned label borrowed from a snapshot.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub struct LabelRef<'m>(&'m str);
+
+impl Display for LabelRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Label for LabelRef<'_> {
+    fn label(&self) -> &str {
+        self.0
+    }
+}
+
+/// A node of a `MappedGraph`. `index` is its position in the node records; the label is borrowed from the snapshot, so
+/// nodes of different snapshots compare by label text.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub struct MappedNode<'m> {
+    id: usize,
+    index: usize,
+    label: LabelRef<'m>,
+}
+
+impl MappedNode<'_> {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl SingleId for MappedNode<'_> {
+    fn id(&self) -> usize {
+        self.id
+    }
+}
+
+impl Display for MappedNode<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[id: {}, label: {}]", self.id, self.label)
+    }
+}
+
+impl Label for MappedNode<'_> {
+    fn label(&self) -> &str {
+        self.label.0
+    }
+}
+
+impl Vertex for MappedNode<'_> {}
+
+/// An edge of a `MappedGraph`, with its label borrowed from the snapshot.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub struct MappedEdge<'m> {
+    src: usize,
+    dst: usize,
+    label: LabelRef<'m>,
+}
+
+impl IdPair for MappedEdge<'_> {
+    fn pair(&self) -> (usize, usize) {
+        (self.src, self.dst)
+    }
+}
+
+impl Display for MappedEdge<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{} -> {}, label: {}]", self.src, self.dst, self.label)
+    }
+}
+
+impl Label for MappedEdge<'_> {
+    fn label(&self) -> &str {
+        self.label.0
+    }
+}
+
+fn push_u64(out: &mut Vec<u8>, value: u64) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn header(kind: u32, counts: [u64; 5]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(HEADER_LEN);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&VERSION.to_le_bytes());
+    out.extend_from_slice(&kind.to_le_bytes());
+    for count in counts {
+        push_u64(&mut out, count);
+    }
+    out
+}
+
+// Labels are stored once each, as an offset table followed by the concatenated text.
+#[derive(Default)]
+struct LabelTable<'s> {
+    labels: Vec<&'s str>,
+    index: HashMap<&'s str, u64>,
+}
+
+impl<'s> LabelTable<'s> {
+    fn intern(&mut self, label: &'s str) -> u64 {
+        *self.index.entry(label).or_insert_with(|| {
+            self.labels.push(label);
+            self.labels.len() as u64 - 1
+        })
+    }
+
+    fn bytes(&self) -> usize {
+        self.labels.iter().map(|label| label.len()).sum()
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        let mut offset = 0;
+        push_u64(out, 0);
+        for label in self.labels.iter() {
+            offset += label.len() as u64;
+            push_u64(out, offset);
+        }
+        for label in self.labels.iter() {
+            out.extend_from_slice(label.as_bytes());
+        }
+        out.resize(out.len().next_multiple_of(8), 0);
+    }
+}
+
+/// Write a labeled graph snapshot. Edges are stored grouped by source node, with CSR offsets indexed by node position.
+pub fn write_graph_snapshot<'a, G>(graph: &'a G, mut writer: impl Write) -> io::Result<()>
+where G: Graph<'a>, G::Node: Label, G::Edge: Label {
+    let nodes = graph.nodes().collect::<Vec<_>>();
+    let index: HashMap<usize, usize> = nodes.iter().enumerate().map(|(i, node)| (node.id(), i)).collect();
+    let mut labels = LabelTable::default();
+    let node_labels = nodes.iter().map(|node| labels.intern(node.label())).collect::<Vec<_>>();
+    let mut edges = Vec::new();
+    for edge in graph.edges() {
+        let (src, dst) = edge.pair();
+        let position = |id: usize| index.get(&id).copied().ok_or_else(|| invalid_data(format!("edge refers to unknown node {}", id)));
+        edges.push((position(src)?, position(dst)?, src, dst, labels.intern(edge.label())));
+    }
+    edges.sort_by_key(|edge| edge.0);
+
+    let mut offsets = vec![0u64; nodes.len() + 1];
+    for edge in edges.iter() {
+        offsets[edge.0 + 1] += 1;
+    }
+    for i in 0..nodes.len() {
+        offsets[i + 1] += offsets[i];
+    }
+    let counts = [nodes.len() as u64, edges.len() as u64, labels.labels.len() as u64, labels.bytes() as u64, 0];
+    let mut out = header(KIND_GRAPH, counts);
+    for (node, label) in nodes.iter().zip(node_labels) {
+        push_u64(&mut out, node.id() as u64);
+        push_u64(&mut out, label);
+    }
+    for &(_, _, src, dst, label) in edges.iter() {
+        push_u64(&mut out, src as u64);
+        push_u64(&mut out, dst as u64);
+        push_u64(&mut out, label);
+    }
+    for offset in offsets {
+        push_u64(&mut out, offset);
+    }
+    for edge in edges.iter() {
+        push_u64(&mut out, edge.1 as u64);
+    }
+    labels.write(&mut out);
+    writer.write_all(&out)
+}
+
+/// Write a hypergraph snapshot: the node records with their descriptors, then CSR offsets and pins of the hyperedges,
+/// their weight, type and label records, and the label table.
+pub fn write_hypergraph_snapshot<'a, H>(hypergraph: &'a H, mut writer: impl Write) -> io::Result<()>
+where H: Hypergraph<'a, Node = Node>, H::Edge: Weighted + Label + Type {
+    let nodes = hypergraph.nodes().collect::<Vec<_>>();
+    let edges = hypergraph.hyperedges().collect::<Vec<_>>();
+    let pins = edges.iter().map(|edge| edge.id()).collect::<Vec<_>>();
+    let pin_count: usize = pins.iter().map(|p| p.len()).sum();
+    let mut labels = LabelTable::default();
+    let edge_labels = edges.iter().map(|edge| labels.intern(edge.label())).collect::<Vec<_>>();
+
+    let counts = [nodes.len() as u64, pins.len() as u64, pin_count as u64, labels.labels.len() as u64, labels.bytes() as u64];
+    let mut out = header(KIND_HYPERGRAPH, counts);
+    for node in nodes {
+        push_u64(&mut out, node.id() as u64);
+        push_u64(&mut out, node.node_type().type_id() as u64);
+        for value in node.desc().values() {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    let mut offset = 0;
+    push_u64(&mut out, 0);
+    for edge in pins.iter() {
+        offset += edge.len() as u64;
+        push_u64(&mut out, offset);
+    }
+    for id in pins.into_iter().flatten() {
+        push_u64(&mut out, id as u64);
+    }
+    for (edge, label) in edges.iter().zip(edge_labels) {
+        push_u64(&mut out, edge.weight().to_bits());
+        push_u64(&mut out, edge.type_id() as u64);
+        push_u64(&mut out, label);
+    }
+    labels.write(&mut out);
+    writer.write_all(&out)
+}
+
+enum Backing {
+    Mapped(Mmap),
+    Owned(Vec<u64>, usize),
+}
+
+/// A snapshot held in memory or mapped from a file. Views borrow from it without copying the large arrays.
+pub struct Snapshot {
+    backing: Backing,
+}
+
+impl Snapshot {
+    /// Map a snapshot file read-only. The file must not be modified while the snapshot is alive.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let map = unsafe { Mmap::map(&file)? };
+        Ok(Snapshot { backing: Backing::Mapped(map) })
+    }
+
+    /// Copy snapshot bytes into an aligned buffer.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut words = vec![0u64; bytes.len().div_ceil(8)];
+        for (word, chunk) in words.iter_mut().zip(bytes.chunks(8)) {
+            let mut buf = [0u8; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            *word = u64::from_ne_bytes(buf);
+        }
+        Snapshot { backing: Backing::Owned(words, bytes.len()) }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        match &self.backing {
+            Backing::Mapped(map) => map,
+            Backing::Owned(words, len) => unsafe { std::slice::from_raw_parts(words.as_ptr() as *const u8, *len) },
+        }
+    }
+
+    fn header(&self, kind: u32) -> io::Result<[usize; 5]> {
+        let bytes = self.bytes();
+        if bytes.len() < HEADER_LEN || &bytes[..8] != MAGIC {
+            return Err(invalid_data("not a graph snapshot"));
+        }
+        let version = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        if version != VERSION {
+            return Err(invalid_data(format!("unsupported snapshot version {} (expected {}); rewrite the snapshot", version, VERSION)));
+        }
+        if u32::from_le_bytes(bytes[12..16].try_into().unwrap()) != kind {
+            return Err(invalid_data("snapshot holds a different kind of graph"));
+        }
+        let mut counts = [0; 5];
+        for (i, count) in counts.iter_mut().enumerate() {
+            let at = 16 + 8 * i;
+            *count = u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap()) as usize;
+        }
+        Ok(counts)
+    }
+
+    /// A read-only view of a labeled graph snapshot. Only the header, the section bounds and the label table are
+    /// checked; call `MappedGraph::validate` on snapshots from untrusted sources, since bad indices panic on access.
+    pub fn graph(&self) -> io::Result<MappedGraph<'_>> {
+        let [n, m, label_count, label_bytes, _] = self.header(KIND_GRAPH)?;
+        let mut sections = Sections::new(self.bytes());
+        let nodes: &[SnapshotNode] = sections.take(n)?;
+        let edges: &[SnapshotEdge] = sections.take(m)?;
+        let offsets: &[u64] = sections.take(n + 1)?;
+        let targets: &[u64] = sections.take(m)?;
+        let labels = sections.take_labels(label_count, label_bytes)?;
+        Ok(MappedGraph::from_parts(nodes, edges, offsets, targets, labels))
+    }
+
+    /// A read-only view of a hypergraph snapshot, checked like `graph`; see `MappedHypergraph::validate`.
+    pub fn hypergraph(&self) -> io::Result<MappedHypergraph<'_>> {
+        let [n, m, pin_count, label_count, label_bytes] = self.header(KIND_HYPERGRAPH)?;
+        let mut sections = Sections::new(self.bytes());
+        let nodes: &[Node] = sections.take(n)?;
+        let offsets: &[u64] = sections.take(m + 1)?;
+        let pins: &[usize] = sections.take(pin_count)?;
+        let records: &[SnapshotHyperedge] = sections.take(m)?;
+        let labels = sections.take_labels(label_count, label_bytes)?;
+        Ok(MappedHypergraph { nodes, offsets, pins, records, labels, hyperedges: OnceLock::new() })
+    }
+}
+
+fn check_labels(labels: impl Iterator<Item = u64>, count: usize) -> io::Result<()> {
+    if labels.into_iter().any(|label| label as usize >= count) {
+        return Err(invalid_data("label index out of range"));
+    }
+    Ok(())
+}
+
+fn check_offsets(offsets: &[u64], total: usize) -> io::Result<()> {
+    if offsets.first() != Some(&0) || offsets.last() != Some(&(total as u64)) || offsets.windows(2).any(|w| w[0] > w[1]) {
+        return Err(invalid_data("corrupt offset table"));
+    }
+    Ok(())
+}
+
+// Cursor over the sections following the header.
+struct Sections<'m> {
+    bytes: &'m [u8],
+    at: usize,
+}
+
+impl<'m> Sections<'m> {
+    fn new(bytes: &'m [u8]) -> Self {
+        Sections { bytes, at: HEADER_LEN }
+    }
+
+    fn take_bytes(&mut self, len: usize) -> io::Result<&'m [u8]> {
+        let end = self.at.checked_add(len).filter(|&end| end <= self.bytes.len()).ok_or_else(|| invalid_data("truncated snapshot"))?;
+        let slice = &self.bytes[self.at..end];
+        self.at = end.next_multiple_of(8);
+        Ok(slice)
+    }
+
+    fn take<T: Plain>(&mut self, count: usize) -> io::Result<&'m [T]> {
+        let len = count.checked_mul(size_of::<T>()).ok_or_else(|| invalid_data("truncated snapshot"))?;
+        let bytes = self.take_bytes(len)?;
+        if !(bytes.as_ptr() as usize).is_multiple_of(align_of::<T>()) {
+            return Err(invalid_data("misaligned snapshot section"));
+        }
+        // SAFETY: `T: Plain` accepts any bit pattern, and the slice is in bounds and aligned.
+        Ok(unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const T, count) })
+    }
+
+    fn take_labels(&mut self, count: usize, len: usize) -> io::Result<Vec<LabelRef<'m>>> {
+        let offsets: &[u64] = self.take(count + 1)?;
+        let text = self.take_bytes(len)?;
+        check_offsets(offsets, len)?;
+        let text = std::str::from_utf8(text).map_err(|_| invalid_data("labels are not valid UTF-8"))?;
+        offsets.windows(2).map(|w| {
+            text.get(w[0] as usize..w[1] as usize).map(LabelRef).ok_or_else(|| invalid_data("label splits a UTF-8 character"))
+        }).collect()
+    }
+}
+
+/// A labeled directed graph read in place from a `Snapshot`. It is read-only: `add_node` and `add_edge` panic.
+///
+/// Neighbour queries read the CSR offsets directly. The snapshot stores only out-edges, so the reverse arrays used by
+/// `get_pre` are built on first use.
+pub struct MappedGraph<'m> {
+    nodes: &'m [SnapshotNode],
+    edges: &'m [SnapshotEdge],
+    offsets: &'m [u64],
+    targets: &'m [u64],
+    labels: Vec<LabelRef<'m>>,
+    // Built on first use, so opening a view stays cheap.
+    node_views: OnceLock<Vec<MappedNode<'m>>>,
+    edge_views: OnceLock<Vec<MappedEdge<'m>>>,
+    predecessors: OnceLock<(Vec<usize>, Vec<usize>)>,
+    statistics: OnceLock<LabelIndex>,
+}
+
+impl<'m> MappedGraph<'m> {
+    fn from_parts(nodes: &'m [SnapshotNode], edges: &'m [SnapshotEdge], offsets: &'m [u64], targets: &'m [u64], labels: Vec<LabelRef<'m>>) -> Self {
+        MappedGraph {
+            nodes,
+            edges,
+            offsets,
+            targets,
+            labels,
+            node_views: OnceLock::new(),
+            edge_views: OnceLock::new(),
+            predecessors: OnceLock::new(),
+            statistics: OnceLock::new(),
+        }
+    }
+
+    /// Check the CSR offsets, edge targets and label indices, in O(n + m).
+    pub fn validate(&self) -> io::Result<()> {
+        check_offsets(self.offsets, self.edges.len())?;
+        if self.targets.iter().any(|&t| t as usize >= self.nodes.len()) {
+            return Err(invalid_data("edge target out of range"));
+        }
+        check_labels(self.nodes.iter().map(|node| node.label).chain(self.edges.iter().map(|edge| edge.label)), self.labels.len())
+    }
+
+    fn node_views(&self) -> &[MappedNode<'m>] {
+        self.node_views.get_or_init(|| self.nodes.iter().enumerate().map(|(index, node)| {
+            MappedNode { id: node.id as usize, index, label: self.labels[node.label as usize].clone() }
+        }).collect())
+    }
+
+    fn edge_views(&self) -> &[MappedEdge<'m>] {
+        self.edge_views.get_or_init(|| self.edges.iter().map(|edge| {
+            MappedEdge { src: edge.src as usize, dst: edge.dst as usize, label: self.labels[edge.label as usize].clone() }
+        }).collect())
+    }
+
+    // Reverse CSR arrays: offsets by destination position, then source positions.
+    fn predecessor_table(&self) -> &(Vec<usize>, Vec<usize>) {
+        self.predecessors.get_or_init(|| {
+            let n = self.nodes.len();
+            let mut offsets = vec![0; n + 1];
+            for &t in self.targets {
+                offsets[t as usize + 1] += 1;
+            }
+            for i in 0..n {
+                offsets[i + 1] += offsets[i];
+            }
+            let mut next = offsets.clone();
+            let mut sources = vec![0; self.targets.len()];
+            for src in 0..n {
+                for &t in self.targets[self.offsets[src] as usize..self.offsets[src + 1] as usize].iter() {
+                    sources[next[t as usize]] = src;
+                    next[t as usize] += 1;
+                }
+            }
+            (offsets, sources)
+        })
+    }
+
+    /// The successors of the node at position `index`, read from the CSR arrays.
+    pub fn successors(&self, index: usize) -> impl Iterator<Item = &MappedNode<'m>> {
+        let nodes = self.node_views();
+        let (start, end) = (self.offsets[index] as usize, self.offsets[index + 1] as usize);
+        self.targets[start..end].iter().map(move |&t| &nodes[t as usize])
+    }
+
+    pub fn predecessors(&self, index: usize) -> impl Iterator<Item = &MappedNode<'m>> {
+        let nodes = self.node_views();
+        let (offsets, sources) = self.predecessor_table();
+        sources[offsets[index]..offsets[index + 1]].iter().map(move |&s| &nodes[s])
+    }
+
+    /// The out-edges of the node at position `index` paired with their destinations.
+    pub fn out_edges(&self, index: usize) -> impl Iterator<Item = (&MappedNode<'m>, &MappedEdge<'m>)> {
+        let (nodes, edges) = (self.node_views(), self.edge_views());
+        (self.offsets[index] as usize..self.offsets[index + 1] as usize).map(move |e| (&nodes[self.targets[e] as usize], &edges[e]))
+    }
+
+    pub fn out_degree(&self, index: usize) -> usize {
+        (self.offsets[index + 1] - self.offsets[index]) as usize
+    }
+
+    fn build_label_index(&self) -> LabelIndex {
+        let mut index = LabelIndex::new();
+        for node in self.nodes {
+            index.add_node(node.id as usize, self.labels[node.label as usize].0);
+        }
+        for edge in self.edges {
+            index.add_edge(edge.src as usize, self.labels[edge.label as usize].0, edge.dst as usize);
+        }
+        index
+    }
+}
+
+impl<'a, 'm: 'a> Graph<'a> for MappedGraph<'m> {
+    type Node = MappedNode<'m>;
+    type Edge = MappedEdge<'m>;
+
+    fn new() -> Self {
+        MappedGraph::from_parts(&[], &[], &[0], &[], Vec::new())
+    }
+
+    fn nodes(&'a self) -> impl Iterator<Item = &'a Self::Node> {
+        self.node_views().iter()
+    }
+
+    fn edges(&'a self) -> impl Iterator<Item = &'a Self::Edge> {
+        self.edge_views().iter()
+    }
+
+    fn add_node(&mut self, _: Self::Node) {
+        panic!("a mapped snapshot graph is read-only")
+    }
+
+    fn add_edge(&mut self, _: Self::Edge) {
+        panic!("a mapped snapshot graph is read-only")
+    }
+}
+
+impl<'a, 'm: 'a> Labeled<'a> for MappedGraph<'m> {
+    fn label_same(&self, node: &Self::Node, label: &Self::Node) -> bool {
+        node.label == label.label
+    }
+
+    fn get_label(&'a self, node: &'a Self::Node) -> &'a impl Label {
+        &node.label
+    }
+
+    fn get_edges_pair_label(&'a self) -> impl Iterator<Item = (&'a Self::Node, &'a Self::Node, &'a impl Label)> {
+        self.node_views().iter().flat_map(move |node| self.out_edges(node.index).map(move |(dst, edge)| (node, dst, &edge.label)))
+    }
+
+    fn edge_label_same(&self, edge1: &Self::Edge, edge2: &Self::Edge) -> bool {
+        edge1.label == edge2.label
+    }
+
+    fn edge_node_label_same(&self, src1: &Self::Node, edge1: &Self::Edge, dst1: &Self::Node, src2: &Self::Node, edge2: &Self::Edge, dst2: &Self::Node) -> bool {
+        self.label_same(src1, src2) && self.edge_label_same(edge1, edge2) && self.label_same(dst1, dst2)
+    }
+}
+
+impl<'a, 'm: 'a> EdgeLabeled<'a> for MappedGraph<'m> {
+    fn get_edge_label(&'a self, edge: &'a Self::Edge) -> &'a impl Label {
+        &edge.label
+    }
+}
+
+impl<'a, 'm: 'a> LabelStatistics<'a> for MappedGraph<'m> {
+    fn label_index(&self) -> &LabelIndex {
+        self.statistics.get_or_init(|| self.build_label_index())
+    }
+
+    fn node_at(&'a self, position: usize) -> &'a Self::Node {
+        &self.node_views()[position]
+    }
+
+    fn edge_at(&'a self, position: usize) -> &'a Self::Edge {
+        &self.edge_views()[position]
+    }
+}
+
+impl Directed for MappedGraph<'_> {}
+
+// Lookups go straight to the CSR arrays, so the adjacency tables are left empty and cost nothing to build.
+impl<'a, 'm: 'a> Adjacency<'a> for MappedGraph<'m> {
+    fn get_adj(&'a self) -> AdjacencyList<'a, Self> {
+        AdjacencyList::default()
+    }
+
+    fn get_post(&'a self, _: &AdjacencyList<'a, Self>, node: &Self::Node) -> impl Iterator<Item = &'a Self::Node> {
+        self.successors(node.index)
+    }
+}
+
+impl<'a, 'm: 'a> AdjacencyInv<'a> for MappedGraph<'m> {
+    fn get_adj_inv(&'a self) -> AdjacencyList<'a, Self> {
+        AdjacencyList::default()
+    }
+
+    fn get_pre(&'a self, _: &AdjacencyList<'a, Self>, node: &Self::Node) -> impl Iterator<Item = &'a Self::Node> {
+        self.predecessors(node.index)
+    }
+}
+
+impl<'a, 'm: 'a> LabeledAdjacency<'a> for MappedGraph<'m> {
+    fn get_labeled_adj(&'a self) -> LabeledAdjacencyList<'a, Self> {
+        LabeledAdjacencyList::default()
+    }
+
+    fn get_labeled_post(&'a self, _: &LabeledAdjacencyList<'a, Self>, node: &Self::Node) -> impl Iterator<Item = (&'a Self::Node, &'a Self::Edge)> {
+        self.out_edges(node.index)
+    }
+}
+
+/// A hyperedge whose members and attributes are borrowed from a snapshot.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub struct MappedHyperedge<'m> {
+    members: &'m [usize],
+    record: &'m SnapshotHyperedge,
+    label: LabelRef<'m>,
+}
+
+impl IdVector for MappedHyperedge<'_> {
+    fn id(&self) -> Vec<usize> {
+        self.members.to_vec()
+    }
+}
+
+impl Hyperedge for MappedHyperedge<'_> {
+    fn id_set(&self) -> std::collections::HashSet<usize> {
+        self.members.iter().copied().collect()
+    }
+}
+
+impl Weighted for MappedHyperedge<'_> {
+    fn weight(&self) -> f64 {
+        f64::from_bits(self.record.weight)
+    }
+}
+
+impl Type for MappedHyperedge<'_> {
+    fn type_id(&self) -> usize {
+        self.record.edge_type as usize
+    }
+}
+
+impl Label for MappedHyperedge<'_> {
+    fn label(&self) -> &str {
+        self.label.0
+    }
+}
+
+impl Display for MappedHyperedge<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.members)?;
+        if !self.label.0.is_empty() {
+            write!(f, "[{}]", self.label)?;
+        }
+        Ok(())
+    }
+}
+
+/// A hypergraph read in place from a `Snapshot`. It is read-only: `add_node` and `add_hyperedge` panic, and algorithms
+/// that build a new hypergraph produce an owned `HypergraphImpl` instead.
+///
+/// `hyperedge` reads one hyperedge from the offsets; `hyperedges` hands out references, so it builds every view on
+/// its first call.
+pub struct MappedHypergraph<'m> {
+    nodes: &'m [Node],
+    offsets: &'m [u64],
+    pins: &'m [usize],
+    records: &'m [SnapshotHyperedge],
+    labels: Vec<LabelRef<'m>>,
+    hyperedges: OnceLock<Vec<MappedHyperedge<'m>>>,
+}
+
+impl<'m> MappedHypergraph<'m> {
+    pub fn hyperedge_count(&self) -> usize {
+        self.records.len()
+    }
+
+    /// The hyperedge at position `index`.
+    pub fn hyperedge(&self, index: usize) -> MappedHyperedge<'m> {
+        let (pins, record) = (self.pins, &self.records[index]);
+        let members = &pins[self.offsets[index] as usize..self.offsets[index + 1] as usize];
+        MappedHyperedge { members, record, label: self.labels[record.label as usize].clone() }
+    }
+
+    /// Check the pin offsets and label indices, in O(m).
+    pub fn validate(&self) -> io::Result<()> {
+        check_offsets(self.offsets, self.pins.len())?;
+        check_labels(self.records.iter().map(|record| record.label), self.labels.len())
+    }
+}
+
+impl<'a, 'm: 'a> Hypergraph<'a> for MappedHypergraph<'m> {
+    type Node = Node;
+    type Edge = MappedHyperedge<'m>;
+
+    fn new() -> Self {
+        MappedHypergraph { nodes: &[], offsets: &[0], pins: &[], records: &[], labels: Vec::new(), hyperedges: OnceLock::new() }
+    }
+
+    fn nodes(&'a self) -> impl Iterator<Item = &'a Self::Node> {
+        self.nodes.iter()
+    }
+
+    fn hyperedges(&'a self) -> impl Iterator<Item = &'a Self::Edge> {
+        self.hyperedges.get_or_init(|| (0..self.hyperedge_count()).map(|i| self.hyperedge(i)).collect()).iter()
+    }
+
+    fn add_node(&mut self, _: Self::Node) {
+        panic!("a mapped snapshot hypergraph is read-only")
+    }
+
+    fn add_hyperedge(&mut self, _: Self::Edge) {
+        panic!("a mapped snapshot hypergraph is read-only")
+    }
+}
+
+impl<'a, 'm: 'a> ToOwnedHypergraph<'a> for MappedHypergraph<'m> {
+    type Owned = HypergraphImpl;
+
+    fn to_owned_node(node: &Node) -> Node {
+        node.clone()
+    }
+
+    fn to_owned_edge(edge: &Self::Edge) -> HyperedgeImpl {
+        HyperedgeImpl::new(edge.id(), edge.weight(), edge.label().to_string(), edge.type_id())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::reduction::Reduction;
+    use crate::algorithms::transversal::Transversal;
+    use crate::impls::standard::{SimpleLabeledGraph, StandardLabeledGraph};
+
+    #[test]
+    fn graph_snapshot_round_trip() {
+        let mut graph = StandardLabeledGraph::new();
+        graph.add_node(7, "a".to_string());
+        graph.add_node(3, "b".to_string());
+        graph.add_node(5, "a".to_string());
+        graph.add_edge(3, 7);
+        graph.add_edge(7, 5);
+        graph.add_edge(7, 3);
+
+        let mut bytes = Vec::new();
+        write_graph_snapshot(&graph, &mut bytes).unwrap();
+        let snapshot = Snapshot::from_bytes(&bytes);
+        let mapped = snapshot.graph().unwrap();
+        mapped.validate().unwrap();
+
+        assert_eq!(mapped.nodes().map(|n| n.id()).collect::<Vec<_>>(), vec![7, 3, 5]);
+        assert_eq!(mapped.successors(0).map(|n| n.id()).collect::<Vec<_>>(), vec![5, 3]);
+        // The tables are empty; lookups read the offsets directly.
+        let first = mapped.nodes().next().unwrap();
+        let (adj, adj_inv, labeled) = (mapped.get_adj(), mapped.get_adj_inv(), mapped.get_labeled_adj());
+        assert_eq!(mapped.get_post(&adj, first).map(|n| n.id()).collect::<Vec<_>>(), vec![5, 3]);
+        assert_eq!(mapped.get_pre(&adj_inv, first).map(|n| n.id()).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(mapped.get_labeled_post(&labeled, first).map(|(n, e)| (n.id(), e.pair())).collect::<Vec<_>>(), vec![(5, (7, 5)), (3, (7, 3))]);
+        assert_eq!(mapped.get_label(first).label(), "a");
+        assert!(mapped.label_same(first, mapped.nodes().nth(2).unwrap()));
+        assert_eq!(mapped.get_edges_pair_label().count(), 3);
+        assert_eq!(mapped.label_index().pair_count("a", "b"), 1);
+        assert_eq!(mapped.label_index().triple_count("a", "", "a"), 1);
+        assert_eq!(mapped.nodes_labeled("a").map(|n| n.id()).collect::<Vec<_>>(), vec![7, 5]);
+    }
+
+    #[test]
+    fn patterns_from_other_snapshots_compare_by_label() {
+        // The pattern interns `b` first, so its label numbering differs from the data snapshot's.
+        let mut data = SimpleLabeledGraph::<String, String>::new();
+        data.add_node(1, "a".to_string());
+        data.add_node(2, "b".to_string());
+        data.add_labeled_edge(1, 2, "x".to_string());
+        let mut pattern = SimpleLabeledGraph::<String, String>::new();
+        pattern.add_node(1, "b".to_string());
+        pattern.add_node(2, "a".to_string());
+        pattern.add_labeled_edge(1, 2, "y".to_string());
+        pattern.add_labeled_edge(2, 1, "x".to_string());
+
+        let (mut data_bytes, mut pattern_bytes) = (Vec::new(), Vec::new());
+        write_graph_snapshot(&data, &mut data_bytes).unwrap();
+        write_graph_snapshot(&pattern, &mut pattern_bytes).unwrap();
+        let (data, pattern) = (Snapshot::from_bytes(&data_bytes), Snapshot::from_bytes(&pattern_bytes));
+        let (data, pattern) = (data.graph().unwrap(), pattern.graph().unwrap());
+
+        assert!(data.label_same(data.node_at(0), pattern.node_at(1)));
+        assert!(!data.label_same(data.node_at(0), pattern.node_at(0)));
+        assert!(!data.edge_label_same(data.edge_at(0), pattern.edge_at(0)));
+        assert!(data.edge_label_same(data.edge_at(0), pattern.edge_at(1)));
+    }
+
+    #[test]
+    fn indices_are_checked_by_validate() {
+        let mut graph = StandardLabeledGraph::new();
+        graph.add_node(0, "a".to_string());
+        graph.add_node(1, "b".to_string());
+        graph.add_edge(0, 1);
+        let mut bytes = Vec::new();
+        write_graph_snapshot(&graph, &mut bytes).unwrap();
+        // The single target follows the header, two node records, one edge record and three offsets.
+        let target = HEADER_LEN + 2 * 16 + 24 + 3 * 8;
+        bytes[target..target + 8].copy_from_slice(&9u64.to_le_bytes());
+
+        let snapshot = Snapshot::from_bytes(&bytes);
+        let mapped = snapshot.graph().unwrap();
+        assert_eq!(mapped.validate().unwrap_err().to_string(), "edge target out of range");
+    }
+
+    #[test]
+    fn hypergraph_snapshot_round_trip() {
+        let mut hypergraph = HypergraphImpl::new();
+        for id in 0..4 {
+            hypergraph.add_node(Node::from_id(id));
+        }
+        hypergraph.add_hyperedge(HyperedgeImpl::new(vec![0, 1, 2], 2.5, "ab".to_string(), 4));
+        hypergraph.add_hyperedge(HyperedgeImpl::new(vec![3], 1.0, String::new(), 0));
+        hypergraph.add_hyperedge(HyperedgeImpl::new(vec![1, 0], -0.5, "ab".to_string(), 1));
+
+        let mut bytes = Vec::new();
+        write_hypergraph_snapshot(&hypergraph, &mut bytes).unwrap();
+        let snapshot = Snapshot::from_bytes(&bytes);
+        let mapped = snapshot.hypergraph().unwrap();
+        mapped.validate().unwrap();
+
+        assert_eq!(mapped.hyperedge_count(), 3);
+        assert_eq!((mapped.hyperedge(2).id(), mapped.hyperedge(2).label()), (vec![1, 0], "ab"));
+        assert!(mapped.nodes().eq(hypergraph.nodes()));
+        assert!(mapped.hyperedges().map(|e| e.id()).eq(hypergraph.hyperedges().map(|e| e.id())));
+        for (m, h) in mapped.hyperedges().zip(hypergraph.hyperedges()) {
+            assert_eq!((m.weight(), m.label(), m.type_id()), (h.weight(), h.label(), h.type_id()));
+        }
+        assert!(snapshot.graph().is_err());
+
+        // Building algorithms give an owned copy instead of writing into the read-only view.
+        assert!(mapped.reduced() == hypergraph.reduced());
+        assert!(mapped.transversal_hypergraph() == hypergraph.transversal_hypergraph());
+    }
+
+    #[test]
+    fn old_versions_are_rejected() {
+        let mut bytes = Vec::new();
+        write_graph_snapshot(&StandardLabeledGraph::new(), &mut bytes).unwrap();
+        bytes[8..12].copy_from_slice(&1u32.to_le_bytes());
+        let err = Snapshot::from_bytes(&bytes).graph().err().unwrap();
+        assert!(err.to_string().contains("unsupported snapshot version 1"));
+    }
+}
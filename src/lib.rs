@@ -3,6 +3,10 @@ use interfaces::hypergraph::{DirectedHypergraph, Hypergraph};
 pub mod interfaces;
 pub mod impls;
 pub mod generator;
+pub mod io;
+pub mod schema;
+pub mod index;
+pub mod algorithms;
 
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
@@ -0,0 +1,327 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use crate::interfaces::graph::{Graph, IdPair, SingleId};
+use crate::interfaces::hypergraph::{Hypergraph, IdVector};
+use crate::interfaces::typed::{Type, Typed};
+
+/// A schema for typed graphs and hypergraphs, given as type ids (`Type::type_id`).
+///
+/// Only what is declared is allowed: node types, `(src type, edge type, dst type)` triples and hyperedge types with
+/// their arity bounds.
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+    node_types: HashSet<usize>,
+    edge_triples: HashSet<(usize, usize, usize)>,
+    hyperedge_arity: HashMap<usize, (usize, Option<usize>)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    UnknownNodeType { node: usize, node_type: usize },
+    DuplicateNode { node: usize },
+    DanglingEdge { edge: usize, src: usize, dst: usize },
+    DisallowedEdge { edge: usize, src: usize, dst: usize, triple: (usize, usize, usize) },
+    UnknownMember { hyperedge: usize, node: usize },
+    UnknownHyperedgeType { hyperedge: usize, hyperedge_type: usize },
+    Arity { hyperedge: usize, hyperedge_type: usize, arity: usize, min: usize, max: Option<usize> },
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::UnknownNodeType { node, node_type } => write!(f, "node {} has undeclared type {}", node, node_type),
+            Violation::DuplicateNode { node } => write!(f, "node id {} is used more than once", node),
+            Violation::DanglingEdge { edge, src, dst } => write!(f, "edge #{} ({} -> {}) refers to a missing node", edge, src, dst),
+            Violation::DisallowedEdge { edge, src, dst, triple: (s, e, d) } => {
+                write!(f, "edge #{} ({} -> {}) has disallowed type triple ({}, {}, {})", edge, src, dst, s, e, d)
+            }
+            Violation::UnknownMember { hyperedge, node } => write!(f, "hyperedge #{} refers to missing node {}", hyperedge, node),
+            Violation::UnknownHyperedgeType { hyperedge, hyperedge_type } => {
+                write!(f, "hyperedge #{} has undeclared type {}", hyperedge, hyperedge_type)
+            }
+            Violation::Arity { hyperedge, hyperedge_type, arity, min, max } => {
+                let max = max.map_or("unbounded".to_string(), |m| m.to_string());
+                write!(f, "hyperedge #{} of type {} has {} members, expected {}..={}", hyperedge, hyperedge_type, arity, min, max)
+            }
+        }
+    }
+}
+
+/// Every violation found by a validation, in the order the elements were visited. Edges and hyperedges are numbered by position.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_valid() {
+            return write!(f, "valid");
+        }
+        writeln!(f, "{} violation(s):", self.violations.len())?;
+        for violation in self.violations.iter() {
+            writeln!(f, "  {}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+// A type id declared in a schema, so graphs can compare their nodes and edges against it through `Typed`.
+struct Declared(usize);
+
+impl Type for Declared {
+    fn type_id(&self) -> usize {
+        self.0
+    }
+}
+
+impl Schema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn allow_node_type(&mut self, node_type: usize) -> &mut Self {
+        self.node_types.insert(node_type);
+        self
+    }
+
+    /// Allow edges of type `edge_type` from nodes of type `src_type` to nodes of type `dst_type`. Both node types are declared too.
+    pub fn allow_edge(&mut self, src_type: usize, edge_type: usize, dst_type: usize) -> &mut Self {
+        self.node_types.insert(src_type);
+        self.node_types.insert(dst_type);
+        self.edge_triples.insert((src_type, edge_type, dst_type));
+        self
+    }
+
+    /// Allow hyperedges of type `hyperedge_type` with between `min` and `max` (inclusive, `None` for no bound) members.
+    pub fn allow_hyperedge(&mut self, hyperedge_type: usize, min: usize, max: Option<usize>) -> &mut Self {
+        self.hyperedge_arity.insert(hyperedge_type, (min, max));
+        self
+    }
+
+    pub fn allows_node(&self, node: &impl Type) -> bool {
+        self.node_types.contains(&node.type_id())
+    }
+
+    pub fn allows_edge(&self, src: &impl Type, edge: &impl Type, dst: &impl Type) -> bool {
+        self.edge_triples.contains(&(src.type_id(), edge.type_id(), dst.type_id()))
+    }
+
+    /// Node ids mapped to their type id.
+    fn check_nodes<'n, N: SingleId + 'n>(&self, nodes: impl Iterator<Item = &'n N>, node_type: impl Fn(&N) -> usize, report: &mut ValidationReport) -> HashMap<usize, usize> {
+        let mut id_map = HashMap::new();
+        for node in nodes {
+            let t = node_type(node);
+            if !self.node_types.contains(&t) {
+                report.violations.push(Violation::UnknownNodeType { node: node.id(), node_type: t });
+            }
+            if id_map.insert(node.id(), t).is_some() {
+                report.violations.push(Violation::DuplicateNode { node: node.id() });
+            }
+        }
+        id_map
+    }
+
+    /// Validate a graph whose nodes and edges implement `Type`.
+    pub fn validate_graph<'a, G>(&self, graph: &'a G) -> ValidationReport
+    where G: Graph<'a>, G::Node: Type, G::Edge: Type {
+        self.validate_graph_by(graph, Type::type_id, Type::type_id)
+    }
+
+    /// Validate a graph that decides itself which types are the same, e.g. one that treats a subtype as its parent type.
+    /// A node or edge is allowed when `Typed::type_same` matches it to a declared type; violations report its own type id.
+    pub fn validate_typed_graph<'a, G>(&self, graph: &'a G) -> ValidationReport
+    where G: Graph<'a> + Typed, G::Node: Type, G::Edge: Type {
+        let mut report = ValidationReport::default();
+        let mut id_map = HashMap::new();
+        for node in graph.nodes() {
+            if !self.node_types.iter().any(|&t| graph.type_same(node, &Declared(t))) {
+                report.violations.push(Violation::UnknownNodeType { node: node.id(), node_type: node.type_id() });
+            }
+            if id_map.insert(node.id(), node).is_some() {
+                report.violations.push(Violation::DuplicateNode { node: node.id() });
+            }
+        }
+        for (i, edge) in graph.edges().enumerate() {
+            let (src, dst) = edge.pair();
+            match (id_map.get(&src), id_map.get(&dst)) {
+                (Some(&s), Some(&d)) => {
+                    let allowed = self.edge_triples.iter().any(|&(st, et, dt)| {
+                        graph.type_same(s, &Declared(st)) && graph.type_same(edge, &Declared(et)) && graph.type_same(d, &Declared(dt))
+                    });
+                    if !allowed {
+                        let triple = (s.type_id(), edge.type_id(), d.type_id());
+                        report.violations.push(Violation::DisallowedEdge { edge: i, src, dst, triple });
+                    }
+                }
+                _ => report.violations.push(Violation::DanglingEdge { edge: i, src, dst }),
+            }
+        }
+        report
+    }
+
+    /// Validate a graph whose node and edge type ids are given by `node_type` and `edge_type`,
+    /// e.g. a labeled graph whose labels stand for types.
+    pub fn validate_graph_by<'a, G: Graph<'a>>(&self, graph: &'a G, node_type: impl Fn(&G::Node) -> usize, edge_type: impl Fn(&G::Edge) -> usize) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let id_map = self.check_nodes(graph.nodes(), node_type, &mut report);
+        for (i, edge) in graph.edges().enumerate() {
+            let (src, dst) = edge.pair();
+            match (id_map.get(&src), id_map.get(&dst)) {
+                (Some(&s), Some(&d)) => {
+                    let triple = (s, edge_type(edge), d);
+                    if !self.edge_triples.contains(&triple) {
+                        report.violations.push(Violation::DisallowedEdge { edge: i, src, dst, triple });
+                    }
+                }
+                _ => report.violations.push(Violation::DanglingEdge { edge: i, src, dst }),
+            }
+        }
+        report
+    }
+
+    pub fn validate_hypergraph<'a, H>(&self, hypergraph: &'a H) -> ValidationReport
+    where H: Hypergraph<'a>, H::Node: Type, H::Edge: Type {
+        let mut report = ValidationReport::default();
+        let id_map = self.check_nodes(hypergraph.nodes(), Type::type_id, &mut report);
+        for (i, edge) in hypergraph.hyperedges().enumerate() {
+            let members = edge.id();
+            for node in members.iter() {
+                if !id_map.contains_key(node) {
+                    report.violations.push(Violation::UnknownMember { hyperedge: i, node: *node });
+                }
+            }
+            let hyperedge_type = edge.type_id();
+            match self.hyperedge_arity.get(&hyperedge_type) {
+                Some(&(min, max)) => {
+                    let arity = members.len();
+                    if arity < min || max.is_some_and(|max| arity > max) {
+                        report.violations.push(Violation::Arity { hyperedge: i, hyperedge_type, arity, min, max });
+                    }
+                }
+                None => report.violations.push(Violation::UnknownHyperedgeType { hyperedge: i, hyperedge_type }),
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::hypergraph::{HyperedgeImpl, HypergraphImpl, Node, NodeType, Desc};
+    use crate::impls::standard::SimpleLabeledGraph;
+    use crate::interfaces::edge::NodeSet;
+    use crate::interfaces::labeled::Label;
+
+    // Type ids in the same block of ten belong to one family, e.g. 10 is a person and 11 an employee.
+    #[derive(Clone, Hash, PartialEq, Eq)]
+    struct Kind(usize, String);
+
+    impl std::fmt::Display for Kind {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.1)
+        }
+    }
+
+    impl Label for Kind {
+        fn label(&self) -> &str {
+            &self.1
+        }
+    }
+
+    impl Type for Kind {
+        fn type_id(&self) -> usize {
+            self.0
+        }
+    }
+
+    impl Typed for SimpleLabeledGraph<Kind, Kind> {
+        fn type_same(&self, x: &impl Type, y: &impl Type) -> bool {
+            x.type_id() / 10 == y.type_id() / 10
+        }
+    }
+
+    #[test]
+    fn reports_every_violation() {
+        let mut hypergraph = HypergraphImpl::new();
+        hypergraph.add_node(Node::new(0, NodeType::new(1), Desc::default()));
+        hypergraph.add_node(Node::new(1, NodeType::new(2), Desc::default()));
+        hypergraph.add_node(Node::new(2, NodeType::new(9), Desc::default()));
+        hypergraph.add_hyperedge(HyperedgeImpl::from_nodes(vec![0, 1]));
+        hypergraph.add_hyperedge(HyperedgeImpl::from_nodes(vec![0, 1, 2, 5]));
+
+        let mut schema = Schema::new();
+        schema.allow_node_type(1).allow_node_type(2).allow_hyperedge(0, 2, Some(3));
+        let report = schema.validate_hypergraph(&hypergraph);
+
+        assert_eq!(report.violations, vec![
+            Violation::UnknownNodeType { node: 2, node_type: 9 },
+            Violation::UnknownMember { hyperedge: 1, node: 5 },
+            Violation::Arity { hyperedge: 1, hyperedge_type: 0, arity: 4, min: 2, max: Some(3) },
+        ]);
+    }
+
+    #[test]
+    fn validates_labeled_graphs() {
+        let mut graph = SimpleLabeledGraph::<String, String>::new();
+        graph.add_node(0, "person".to_string());
+        graph.add_node(1, "company".to_string());
+        graph.add_node(1, "person".to_string());
+        graph.add_labeled_edge(0, 1, "works_at".to_string());
+        graph.add_labeled_edge(1, 0, "works_at".to_string());
+        graph.add_labeled_edge(0, 7, "knows".to_string());
+
+        let types = ["person", "company", "works_at", "knows"];
+        let type_of = |label: &String| types.iter().position(|t| t == label).unwrap();
+        let mut schema = Schema::new();
+        schema.allow_edge(0, 2, 1).allow_edge(0, 3, 0);
+        let report = schema.validate_graph_by(&graph, |node| type_of(node.label_value()), |edge| type_of(edge.label_value()));
+
+        // The duplicate id keeps the type of its last node, so both `works_at` edges see a person at node 1.
+        assert_eq!(report.violations, vec![
+            Violation::DuplicateNode { node: 1 },
+            Violation::DisallowedEdge { edge: 0, src: 0, dst: 1, triple: (0, 2, 0) },
+            Violation::DisallowedEdge { edge: 1, src: 1, dst: 0, triple: (0, 2, 0) },
+            Violation::DanglingEdge { edge: 2, src: 0, dst: 7 },
+        ]);
+
+        let mut valid = SimpleLabeledGraph::<String, String>::new();
+        valid.add_node(0, "person".to_string());
+        valid.add_node(1, "company".to_string());
+        valid.add_labeled_edge(0, 1, "works_at".to_string());
+        assert!(schema.validate_graph_by(&valid, |node| type_of(node.label_value()), |edge| type_of(edge.label_value())).is_valid());
+    }
+
+    #[test]
+    fn typed_graphs_decide_type_equality() {
+        let kind = |t: usize, name: &str| Kind(t, name.to_string());
+        let mut graph = SimpleLabeledGraph::<Kind, Kind>::new();
+        graph.add_node(0, kind(11, "employee"));
+        graph.add_node(1, kind(20, "company"));
+        graph.add_node(2, kind(35, "robot"));
+        graph.add_labeled_edge(0, 1, kind(41, "works_at"));
+        graph.add_labeled_edge(1, 0, kind(40, "works_at"));
+        graph.add_labeled_edge(0, 9, kind(40, "works_at"));
+
+        let mut schema = Schema::new();
+        schema.allow_edge(10, 40, 20);
+        let report = schema.validate_typed_graph(&graph);
+
+        // An employee is a person and a subtype of `works_at` is still `works_at`; only the robot and the reversed edge fail.
+        assert_eq!(report.violations, vec![
+            Violation::UnknownNodeType { node: 2, node_type: 35 },
+            Violation::DisallowedEdge { edge: 1, src: 1, dst: 0, triple: (20, 40, 11) },
+            Violation::DanglingEdge { edge: 2, src: 0, dst: 9 },
+        ]);
+        // Compared by exact type id, the employee and the `works_at` subtype are rejected too.
+        assert_eq!(schema.validate_graph(&graph).violations.len(), 5);
+    }
+}